@@ -0,0 +1,222 @@
+//! A tiny arithmetic expression evaluator.
+//!
+//! Supports `+`, `-`, `*`, `/`, parentheses, and unary minus on floating
+//! point numbers with the usual precedence.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+/////////////////////////////////////////////////////////////////////////
+// Definitions
+/////////////////////////////////////////////////////////////////////////
+
+/// A recursive descent parser that evaluates as it goes.
+struct Evaluator<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+/////////////////////////////////////////////////////////////////////////
+// Implementations
+/////////////////////////////////////////////////////////////////////////
+
+impl<'a> Evaluator<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+        }
+    }
+
+    /// Returns the next non-whitespace character without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while let Some(c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.chars.next();
+            } else {
+                return Some(*c);
+            }
+        }
+        None
+    }
+
+    fn unexpected(&mut self) -> String {
+        match self.peek() {
+            Some(c) => format!("invalid expression: unexpected `{}`", c),
+            None => "invalid expression: unexpected end of input".to_string(),
+        }
+    }
+
+    /// expr = term (("+" | "-") term)*
+    fn expr(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some('+') => {
+                    self.chars.next();
+                    value += self.term()?;
+                }
+                Some('-') => {
+                    self.chars.next();
+                    value -= self.term()?;
+                }
+                _ => break Ok(value),
+            }
+        }
+    }
+
+    /// term = factor (("*" | "/") factor)*
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.factor()?;
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.chars.next();
+                    value *= self.factor()?;
+                }
+                Some('/') => {
+                    self.chars.next();
+                    let divisor = self.factor()?;
+                    if divisor == 0.0 {
+                        return Err("invalid expression: division by zero".to_string());
+                    }
+                    value /= divisor;
+                }
+                _ => break Ok(value),
+            }
+        }
+    }
+
+    /// factor = "-" factor | "(" expr ")" | number
+    fn factor(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Ok(-self.factor()?)
+            }
+            Some('(') => {
+                self.chars.next();
+                let value = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(value)
+                    }
+                    _ => Err(self.unexpected()),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.peek() {
+                    if c.is_ascii_digit() || *c == '.' {
+                        number.push(*c);
+                        self.chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                number
+                    .parse()
+                    .map_err(|_| format!("invalid expression: bad number `{}`", number))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+/// Evaluate a simple arithmetic expression.
+///
+/// Returns `None` if the input does not look like an expression, that is it
+/// does not contain any operators.
+pub fn evaluate(input: &str) -> Option<Result<f64, String>> {
+    let is_expression = input.starts_with('(')
+        || input
+            .chars()
+            .skip(1)
+            .any(|c| matches!(c, '+' | '-' | '*' | '/' | '(' | ')'));
+    if !is_expression {
+        return None;
+    }
+    let mut evaluator = Evaluator::new(input);
+    let result = evaluator.expr().and_then(|value| match evaluator.peek() {
+        Some(_) => Err(evaluator.unexpected()),
+        None => Ok(value),
+    });
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str) -> Result<f64, String> {
+        evaluate(input).expect("not an expression")
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("10 - 4 / 2"), Ok(8.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("2 * 3 / 4"), Ok(1.5));
+    }
+
+    #[test]
+    fn unary_minus() {
+        assert_eq!(eval("-2 * 3"), Ok(-6.0));
+        assert_eq!(eval("4 - -2"), Ok(6.0));
+        assert_eq!(eval("--2+0"), Ok(2.0));
+        assert_eq!(eval("-(1 + 2)"), Ok(-3.0));
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("((2))"), Ok(2.0));
+        assert_eq!(eval("2 * (3 + (4 - 1))"), Ok(12.0));
+        assert_eq!(
+            eval("(1 + 2"),
+            Err("invalid expression: unexpected end of input".to_string())
+        );
+        assert_eq!(
+            eval("1 + 2)"),
+            Err("invalid expression: unexpected `)`".to_string())
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let err = Err("invalid expression: division by zero".to_string());
+        assert_eq!(eval("1 / 0"), err);
+        assert_eq!(eval("1 / (2 - 2)"), err);
+    }
+
+    #[test]
+    fn trailing_garbage() {
+        assert_eq!(
+            eval("1 + 2 x"),
+            Err("invalid expression: unexpected `x`".to_string())
+        );
+        assert_eq!(
+            eval("1 +"),
+            Err("invalid expression: unexpected end of input".to_string())
+        );
+        assert_eq!(
+            eval("1e3+1"),
+            Err("invalid expression: unexpected `e`".to_string())
+        );
+        assert_eq!(
+            eval("1.2.3 * 2"),
+            Err("invalid expression: bad number `1.2.3`".to_string())
+        );
+    }
+
+    #[test]
+    fn expression_detection() {
+        assert_eq!(evaluate("42"), None);
+        assert_eq!(evaluate("-42"), None);
+        assert_eq!(evaluate("1.5"), None);
+        assert_eq!(evaluate("abc"), None);
+        assert_eq!(evaluate("(42)"), Some(Ok(42.0)));
+        assert_eq!(evaluate("1024*8"), Some(Ok(8192.0)));
+        assert_eq!(evaluate("-1-1"), Some(Ok(-2.0)));
+    }
+}
@@ -38,60 +38,485 @@
 //! [`.matches()`]: struct.Input.html#method.matches
 //! [`confirm`]: fn.confirm.html
 
+#[cfg(feature = "arithmetic")]
+mod arithmetic;
+#[cfg(feature = "sticky")]
+mod sticky;
+mod term;
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::error::Error;
 use std::fmt::{self, Debug, Display};
-use std::io::{self, Write};
-use std::str::FromStr;
+use std::io::{self, BufRead, IsTerminal, Read, StdinLock, Write};
+use std::ops::{Bound, Range, RangeBounds, RangeInclusive, Sub};
+use std::rc::Rc;
+use std::str::{self, FromStr};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /////////////////////////////////////////////////////////////////////////
 // Definitions
 /////////////////////////////////////////////////////////////////////////
 
+/// An error that can occur when reading input.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CasualError {
+    /// An I/O error occurred while reading input or writing output.
+    Io(io::Error),
+    /// The end of input was reached and there was no default.
+    Eof,
+    /// The input was empty.
+    Empty,
+    /// The input could not be parsed, contains the error message.
+    Parse(String),
+    /// The input was parsed but it was rejected, contains the error message.
+    Validation(String),
+    /// The user cancelled input, for example by pressing Ctrl-C or entering
+    /// one of the words given to
+    /// [`Input::abort_on()`](struct.Input.html#method.abort_on).
+    Cancelled,
+    /// The maximum number of attempts was reached without valid input.
+    TooManyAttempts,
+}
+
+/// The outcome of a single attempt at reading input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// The input was empty and there was no default.
+    Empty,
+    /// The input could not be parsed, contains the error message.
+    ParseError(String),
+    /// The input was parsed but it was rejected, contains the error message.
+    Invalid(String),
+    /// The input was accepted.
+    Accepted,
+}
+
+/// A range entered by the user.
+///
+/// Returned by [`prompt_range`](fn.prompt_range.html).
+///
+/// # Examples
+///
+/// ```
+/// # use casual::InputRange;
+/// let range = InputRange::Inclusive(0u8..=255);
+/// assert!(range.contains(&255));
+/// assert_eq!((range.start(), range.end()), (&0, &255));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputRange<T> {
+    /// A range entered as `start..end`, which excludes the end.
+    Exclusive(Range<T>),
+    /// A range entered as `start..=end`, which includes the end.
+    Inclusive(RangeInclusive<T>),
+}
+
+/// Summary statistics for a list of numbers.
+///
+/// Returned by [`prompt_numbers`](fn.prompt_numbers.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// The smallest number.
+    pub min: f64,
+    /// The largest number.
+    pub max: f64,
+    /// The sum of all the numbers.
+    pub sum: f64,
+    /// The arithmetic mean of the numbers.
+    pub mean: f64,
+}
+
+/// A yes or no question builder.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::Confirm;
+/// if Confirm::new("Install updates?").default(true).get() {
+///     // install
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Confirm {
+    text: String,
+    default: bool,
+    yes: String,
+    no: String,
+}
+
+/// An iterator over values parsed from each line of stdin.
+///
+/// Returned by [`lines`](fn.lines.html).
+pub struct Lines<T> {
+    skip_blank: bool,
+    done: bool,
+    validators: Vec<Validator<T>>,
+}
+
+/// Reads input for several prompts from the same reader.
+///
+/// By default this reads from stdin, see [`Reader::new()`](#method.new).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::Reader;
+/// let mut reader = Reader::new();
+/// let name: String = reader.prompt("Name: ");
+/// let age: u32 = reader.prompt("Age: ");
+/// ```
+#[derive(Debug)]
+pub struct Reader<R = StdinLock<'static>> {
+    /// The reader to use, or `None` to lock stdin for each read.
+    reader: Option<R>,
+    min_interval: Option<Duration>,
+    /// When the last successful read finished.
+    last_read: Option<Instant>,
+}
+
+/// A function that checks something about raw user input.
+type PredicateFn = dyn Fn(&str) -> bool + 'static;
+
+/// A function that transforms raw user input.
+type TransformFn = dyn Fn(&str) -> String + 'static;
+
+/// A function that computes the valid options for user input.
+type OptionsFn = dyn Fn() -> Vec<String> + 'static;
+
+/// A function that displays an error message.
+type ErrorFn = dyn Fn(&str) + 'static;
+
+/// A function that displays a prompt and reads a line of user input.
+type ReadLineFn<'a> = dyn FnMut(&Option<String>, &mut dyn Write) -> io::Result<String> + 'a;
+
+/// A function that computes a default value.
+type DefaultFn<T> = dyn Fn() -> T + 'static;
+
+/// A function that is called with an accepted value.
+type AcceptFn<T> = dyn Fn(&T) + 'static;
+
+/// A function that validates user input.
+type ValidateFn<T> = dyn Fn(&T) -> Result<(), String> + 'static;
+
 /// A validator for user input.
 struct Validator<T> {
-    raw: Box<dyn Fn(&T) -> bool + 'static>,
+    raw: Box<ValidateFn<T>>,
 }
 
-/// An input builder.
-pub struct Input<T> {
+/// A function that parses user input.
+type ParseFn<T> = dyn Fn(&str) -> Result<T, String> + 'static;
+
+/// A function that parses user input, reporting where an error occurred.
+type DetailedParseFn<T> = dyn Fn(&str) -> Result<T, (usize, String)> + 'static;
+
+/// A parser for user input.
+enum Parser<T> {
+    /// Parse using the type's `FromStr` implementation.
+    FromStr(fn(&str) -> Result<T, String>),
+    /// Parse using a custom function.
+    Custom(Box<ParseFn<T>>),
+    /// Parse using a custom function that reports the error position.
+    Detailed(Box<DetailedParseFn<T>>),
+}
+
+/// A budget that accepted values are subtracted from.
+struct Budget<T> {
+    /// Returns the remaining budget for display.
+    remaining: Box<dyn Fn() -> String>,
+    /// Subtracts an accepted value from the budget.
+    spend: Box<AcceptFn<T>>,
+}
+
+/// How user input is trimmed.
+enum Trim {
+    /// Trim leading and trailing whitespace.
+    Whitespace,
+    /// Trim the given characters.
+    Chars(Vec<char>),
+    /// Only remove the line ending.
+    Off,
+}
+
+/// A limit on the length of a line of user input.
+#[derive(Debug, Clone, Copy)]
+struct ReadLimit {
+    bytes: usize,
+    truncate: bool,
+}
+
+/// The error used when a line is longer than the read limit.
+#[derive(Debug)]
+struct LineTooLong(usize);
+
+/// Where prompts and messages are written.
+struct Output<'a> {
+    out: &'a mut dyn Write,
+    err: Option<&'a mut dyn Write>,
+    /// Whether `out` is a terminal that can be redrawn.
+    interactive: bool,
+}
+
+/// Settings that control how user input is read.
+#[derive(Default)]
+struct ReadOptions {
+    multiline: bool,
+    terminator: Option<String>,
+    line_validators: Vec<Box<ValidateFn<str>>>,
+    limit: Option<ReadLimit>,
+    remind: Option<Duration>,
+    timeout: Option<Duration>,
+    countdown: bool,
+    secret: bool,
+    mask: Option<char>,
+    max_attempts: Option<usize>,
+}
+
+/// Settings that control how the prompt and messages are displayed.
+#[derive(Default)]
+struct ViewOptions {
     prompt: Option<String>,
     prefix: Option<String>,
     suffix: Option<String>,
+    required: Option<String>,
+    context: Vec<String>,
+    help: Vec<String>,
+    start: Option<Box<dyn FnOnce()>>,
+    on_error: Option<Box<ErrorFn>>,
+    prompt_style: Option<Box<TransformFn>>,
+    error_style: Option<Box<TransformFn>>,
+    stderr: bool,
+}
+
+/// Settings that control how raw user input is interpreted before it is
+/// parsed.
+#[derive(Default)]
+struct TextOptions {
+    empty: Option<Box<PredicateFn>>,
+    /// How input is trimmed, or `None` to trim a single line but not a block.
+    trim: Option<Trim>,
+    whitespace_is_invalid: bool,
+    empty_is_error: bool,
+    deprecated: Vec<(String, String)>,
+    number_words: bool,
+    round_floats: bool,
+    #[cfg(feature = "arithmetic")]
+    arithmetic: bool,
+    preview: Option<Rc<TransformFn>>,
+    display: Option<Rc<TransformFn>>,
+    transforms: Vec<Box<TransformFn>>,
+    fold: Option<Box<TransformFn>>,
+    options: Option<Box<OptionsFn>>,
+    length_limits: Option<(usize, usize)>,
+    corrections: Vec<(String, String)>,
+    abort: Vec<String>,
+}
+
+/// An input builder.
+pub struct Input<T> {
+    view: ViewOptions,
+    read: ReadOptions,
+    text: TextOptions,
     default: Option<T>,
-    validator: Option<Validator<T>>,
+    default_with: Option<Box<DefaultFn<T>>>,
+    validators: Vec<Validator<T>>,
+    parser: Parser<T>,
+    debug: Option<fn(&T) -> String>,
+    show_default: Option<fn(&T) -> String>,
+    budget: Option<Budget<T>>,
+    save: Option<Box<AcceptFn<T>>>,
+    fallback: Option<Box<Input<T>>>,
 }
 
 /////////////////////////////////////////////////////////////////////////
 // Implementations
 /////////////////////////////////////////////////////////////////////////
 
+impl Display for CasualError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Eof => write!(f, "unexpected end of input"),
+            Self::Empty => write!(f, "empty input"),
+            Self::Parse(msg) | Self::Validation(msg) => write!(f, "{}", msg),
+            Self::Cancelled => write!(f, "input was cancelled"),
+            Self::TooManyAttempts => write!(f, "too many invalid attempts"),
+        }
+    }
+}
+
+impl Error for CasualError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Eof
+            | Self::Empty
+            | Self::Parse(_)
+            | Self::Validation(_)
+            | Self::Cancelled
+            | Self::TooManyAttempts => None,
+        }
+    }
+}
+
+impl CasualError {
+    /// Returns whether the user abandoned the input, in which case a fallback
+    /// input is used instead.
+    fn is_abandoned(&self) -> bool {
+        match self {
+            Self::Cancelled => true,
+            Self::Io(err) => err.kind() == io::ErrorKind::TimedOut,
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for CasualError {
+    fn from(err: io::Error) -> Self {
+        match term::is_interrupt(&err) {
+            true => Self::Cancelled,
+            false => Self::Io(err),
+        }
+    }
+}
+
+impl Display for LineTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "input line exceeds {} bytes", self.0)
+    }
+}
+
+impl Error for LineTooLong {}
+
 impl<T> Validator<T> {
     /// Construct a new `Validator`.
     fn new<F>(raw: F) -> Self
     where
-        F: Fn(&T) -> bool + 'static,
+        F: Fn(&T) -> Result<(), String> + 'static,
     {
         Self { raw: Box::new(raw) }
     }
 
+    /// Construct a new `Validator` from a predicate that displays the given
+    /// message when it fails.
+    fn with_message<F, S>(matches: F, message: S) -> Self
+    where
+        F: Fn(&T) -> bool + 'static,
+        S: Into<String>,
+    {
+        let message = message.into();
+        Self::new(move |input| {
+            if matches(input) {
+                Ok(())
+            } else {
+                Err(message.clone())
+            }
+        })
+    }
+
     /// Run the validator on the given input.
-    fn run(&self, input: &T) -> bool {
+    fn run(&self, input: &T) -> Result<(), String> {
         (self.raw)(input)
     }
 }
 
+impl<T> Parser<T> {
+    /// Construct a new `Parser` that uses the given function.
+    fn new<F>(raw: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        Parser::Custom(Box::new(raw))
+    }
+
+    /// Construct a new `Parser` that uses [`FromStr`].
+    fn from_str() -> Self
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        Parser::FromStr(|s| s.parse().map_err(|err: T::Err| err.to_string()))
+    }
+
+    /// Run the parser on the given input.
+    fn run(&self, input: &str) -> Result<T, String> {
+        self.run_detailed(input).map_err(|(_, msg)| msg)
+    }
+
+    /// Run the parser on the given input, returning the error position if
+    /// the parser reports one.
+    fn run_detailed(&self, input: &str) -> Result<T, (Option<usize>, String)> {
+        match self {
+            Parser::FromStr(raw) => raw(input).map_err(|msg| (None, msg)),
+            Parser::Custom(raw) => raw(input).map_err(|msg| (None, msg)),
+            Parser::Detailed(raw) => raw(input).map_err(|(pos, msg)| (Some(pos), msg)),
+        }
+    }
+}
+
+impl<'a> Output<'a> {
+    /// Construct a new `Output` that writes to the standard streams.
+    ///
+    /// Prompts and errors are written to stdout, or to stderr if `to_stderr`
+    /// is set. Warnings are always written to stderr.
+    fn std(to_stderr: bool, stdout: &'a mut io::Stdout, stderr: &'a mut io::Stderr) -> Self {
+        if to_stderr {
+            Self {
+                out: stderr,
+                err: None,
+                interactive: io::stdin().is_terminal() && io::stderr().is_terminal(),
+            }
+        } else {
+            Self {
+                out: stdout,
+                err: Some(stderr),
+                interactive: term::is_interactive(),
+            }
+        }
+    }
+
+    /// Where prompts and errors are written.
+    fn out(&mut self) -> &mut dyn Write {
+        self.out
+    }
+
+    /// Where warnings are written.
+    fn err(&mut self) -> &mut dyn Write {
+        match &mut self.err {
+            Some(err) => *err,
+            None => self.out,
+        }
+    }
+}
+
 impl<T: Debug> Debug for Input<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Input")
-            .field("prefix", &self.prefix)
-            .field("prompt", &self.prompt)
-            .field("suffix", &self.suffix)
+            .field("prefix", &self.view.prefix)
+            .field("prompt", &self.view.prompt)
+            .field("suffix", &self.view.suffix)
             .field("default", &self.default)
+            .field("required", &self.view.required)
+            .field("limit", &self.read.limit)
+            .field("remind", &self.read.remind)
+            .field("timeout", &self.read.timeout)
+            .field("countdown", &self.read.countdown)
+            .field("fallback", &self.fallback)
             .finish() // FIXME rust-lang/rust#67364:
                       // use .finish_non_exhaustive() when it's stabilized
     }
 }
 
-impl<T> Default for Input<T> {
+impl<T> Default for Input<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
     /// Construct a new empty `Input`.
     ///
     /// Identical to [`Input::new()`](struct.Input.html#method.new).
@@ -100,35 +525,125 @@ impl<T> Default for Input<T> {
     }
 }
 
-impl<T> Input<T> {
+impl<T> Input<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
     /// Construct a new empty `Input`.
     ///
+    /// The input is parsed using [`FromStr`], so `T` must implement it when
+    /// the `Input` is constructed rather than when it is read. To read a type
+    /// that doesn't implement it use
+    /// [`Input::with_parse()`](struct.Input.html#method.with_parse).
+    ///
     /// Identical to [`Input::default()`](struct.Input.html#impl-Default).
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
     pub fn new() -> Self {
+        Self::from_parser(Parser::from_str())
+    }
+}
+
+impl<T> Input<T> {
+    /// Construct a new empty `Input` that parses the input using the given
+    /// function instead of [`FromStr`].
+    ///
+    /// This makes it possible to read types that don't implement [`FromStr`].
+    /// If `parse` returns an error it is displayed and the user is prompted
+    /// again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// use std::time::Duration;
+    ///
+    /// let mut transcript = Vec::new();
+    /// let delay = Input::with_parse(|s| s.parse().map(Duration::from_secs))
+    ///     .prompt("Delay in seconds: ")
+    ///     .get_from_rw(&b"soon\n5\n"[..], &mut transcript)?;
+    /// assert_eq!(delay, Duration::from_secs(5));
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Delay in seconds: Error: invalid digit found in string\nDelay in seconds: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    pub fn with_parse<E, F>(parse: F) -> Self
+    where
+        E: Display,
+        F: Fn(&str) -> Result<T, E> + 'static,
+    {
+        Self::from_parser(Parser::new(move |s| {
+            parse(s).map_err(|err| err.to_string())
+        }))
+    }
+
+    /// Construct a new empty `Input` that parses the input using a function
+    /// that reports where an error occurred.
+    ///
+    /// This is the same as [`.parse_detailed()`](#method.parse_detailed)
+    /// except that it doesn't require an `Input<String>` first.
+    pub fn with_parse_detailed<F>(parse: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, (usize, String)> + 'static,
+    {
+        Self::from_parser(Parser::Detailed(Box::new(parse)))
+    }
+
+    /// Construct a new empty `Input` that uses the given parser.
+    fn from_parser(parser: Parser<T>) -> Self {
         Self {
-            prefix: None,
-            prompt: None,
-            suffix: None,
+            view: ViewOptions::default(),
+            read: ReadOptions::default(),
+            text: TextOptions::default(),
             default: None,
-            validator: None,
+            default_with: None,
+            validators: Vec::new(),
+            parser,
+            debug: None,
+            show_default: None,
+            budget: None,
+            save: None,
+            fallback: None,
+        }
+    }
+
+    /// Replace the parser, changing the type of the value.
+    ///
+    /// The new parser is constructed from the current parser and validators.
+    /// Other settings that depend on the type of the value, like the default,
+    /// are discarded.
+    fn map_parser<U, F>(self, f: F) -> Input<U>
+    where
+        F: FnOnce(Parser<T>, Vec<Validator<T>>) -> Parser<U>,
+    {
+        Input {
+            view: self.view,
+            read: self.read,
+            text: self.text,
+            ..Input::from_parser(f(self.parser, self.validators))
         }
     }
 
     /// Set the prompt to display before waiting for user input.
     pub fn prompt<S: Into<String>>(mut self, prompt: S) -> Self {
-        self.prompt = Some(prompt.into());
+        self.view.prompt = Some(prompt.into());
         self
     }
 
     /// Set the prompt prefix.
     pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
-        self.prefix = Some(prefix.into());
+        self.view.prefix = Some(prefix.into());
         self
     }
 
     /// Set the prompt suffix.
     pub fn suffix<S: Into<String>>(mut self, suffix: S) -> Self {
-        self.suffix = Some(suffix.into());
+        self.view.suffix = Some(suffix.into());
         self
     }
 
@@ -138,13 +653,46 @@ impl<T> Input<T> {
     /// input.
     pub fn default(mut self, default: T) -> Self {
         self.default = Some(default);
+        self.default_with = None;
+        self
+    }
+
+    /// Set a function that computes the default value.
+    ///
+    /// The function is only called in the event the user enters an empty
+    /// input, which is useful if the default is expensive to compute. This
+    /// replaces any default that is already set, and likewise
+    /// [`.default()`](#method.default) replaces this. A computed default is
+    /// not displayed by [`.show_default()`](#method.show_default).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let user: String = Input::new()
+    ///     .prompt("User: ")
+    ///     .default_with(|| std::env::var("CASUAL_USER").unwrap_or("guest".to_string()))
+    ///     .get_from_rw(&b"\n"[..], &mut transcript)?;
+    /// assert_eq!(user, "guest");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn default_with<F>(mut self, default: F) -> Self
+    where
+        F: Fn() -> T + 'static,
+    {
+        self.default = None;
+        self.default_with = Some(Box::new(default));
         self
     }
 
     /// Check input values.
     ///
     /// If set, this function will be called on the parsed user input and only
-    /// if it passes will we return the value.
+    /// if it passes will we return the value. This can be called multiple
+    /// times, and combined with the other validation methods, in which case
+    /// the input must pass every check. The first check that fails, in the
+    /// order they were added, determines the error message.
     ///
     /// # Examples
     ///
@@ -156,120 +704,3113 @@ impl<T> Input<T> {
     where
         F: Fn(&T) -> bool + 'static,
     {
-        self.validator = Some(Validator::new(matches));
+        self.validators
+            .push(Validator::with_message(matches, "invalid input"));
         self
     }
-}
-
-fn read_line(prompt: &Option<String>) -> io::Result<String> {
-    if let Some(prompt) = prompt {
-        let mut stdout = io::stdout();
-        stdout.write_all(prompt.as_bytes())?;
-        stdout.flush()?;
-    }
-    let mut result = String::new();
-    io::stdin().read_line(&mut result)?;
-    Ok(result)
-}
 
-impl<T> Input<T>
-where
-    T: FromStr,
-    <T as FromStr>::Err: Display,
-{
-    fn try_get_with<F>(self, read_line: F) -> io::Result<T>
+    /// Check input values, displaying a custom error message.
+    ///
+    /// This is the same as [`.matches()`](#method.matches) except that the
+    /// function returns the error message to display when the input is
+    /// invalid, instead of the generic "invalid input".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let age: u32 = Input::new()
+    ///     .prompt("Age: ")
+    ///     .matches_with(|age| match *age < 120 {
+    ///         true => Ok(()),
+    ///         false => Err("age must be under 120".to_string()),
+    ///     })
+    ///     .get_from_rw(&b"130\n30\n"[..], &mut transcript)?;
+    /// assert_eq!(age, 30);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Age: Error: age must be under 120\nAge: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn matches_with<F>(mut self, matches: F) -> Self
     where
-        F: Fn(&Option<String>) -> io::Result<String>,
+        F: Fn(&T) -> Result<(), String> + 'static,
     {
-        let Self {
-            prompt,
-            prefix,
-            suffix,
-            default,
-            validator,
-        } = self;
-
-        let prompt = prompt.map(move |prompt| {
-            let mut p = String::new();
-            if let Some(prefix) = prefix {
-                p.push_str(&prefix);
-            }
-            p.push_str(&prompt);
-            if let Some(suffix) = suffix {
-                p.push_str(&suffix);
-            }
-            p
-        });
-
-        Ok(loop {
-            match read_line(&prompt)?.trim() {
-                "" => {
-                    if let Some(default) = default {
-                        break default;
-                    } else {
-                        continue;
-                    }
-                }
-                raw => match raw.parse() {
-                    Ok(result) => {
-                        if let Some(validator) = &validator {
-                            if !validator.run(&result) {
-                                println!("Error: invalid input");
-                                continue;
-                            }
-                        }
-                        break result;
-                    }
-                    Err(err) => {
-                        println!("Error: {}", err);
-                        continue;
-                    }
-                },
-            }
-        })
+        self.validators.push(Validator::new(matches));
+        self
     }
 
-    #[inline]
-    fn try_get(self) -> io::Result<T> {
-        self.try_get_with(read_line)
+    /// Check input values, suggesting a correction if they are invalid.
+    ///
+    /// This is the same as [`.matches_with()`](#method.matches_with) except
+    /// that the function returns a suggested value instead of an error
+    /// message, which is displayed as "invalid input, did you mean '...'?".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let answer: String = Input::new()
+    ///     .prompt("Answer: ")
+    ///     .matches_or_suggest(|s: &String| match s.as_str() {
+    ///         "Yes" | "No" => Ok(()),
+    ///         s if s.to_lowercase().starts_with('y') => Err("Yes".to_string()),
+    ///         _ => Err("No".to_string()),
+    ///     })
+    ///     .get_from_rw(&b"Yse\nYes\n"[..], &mut transcript)?;
+    /// assert_eq!(answer, "Yes");
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Answer: Error: invalid input, did you mean 'Yes'?\nAnswer: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn matches_or_suggest<F>(mut self, matches: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        self.validators.push(Validator::new(move |value: &T| {
+            matches(value)
+                .map_err(|suggestion| format!("invalid input, did you mean '{}'?", suggestion))
+        }));
+        self
     }
 
-    /// Consumes the `Input` and reads the input from the user.
+    /// Check input values against a checksum.
     ///
-    /// This function uses [`FromStr`] to parse the input data.
+    /// This is the same as [`.matches()`](#method.matches) except that a
+    /// "checksum failed" error is displayed if the check fails. See
+    /// [`luhn`](fn.luhn.html) for a common checksum.
+    ///
+    /// # Examples
     ///
     /// ```no_run
-    /// # use casual::Input;
-    /// let num: u32 = Input::new().prompt("Enter a number: ").get();
+    /// # use casual::{luhn, Input};
+    /// let card: String = Input::new()
+    ///     .prompt("Card number: ")
+    ///     .checksum(luhn)
+    ///     .get();
     /// ```
-    ///
-    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
-    pub fn get(self) -> T {
-        self.try_get().unwrap()
+    pub fn checksum<F>(mut self, checksum: F) -> Self
+    where
+        F: Fn(&T) -> bool + 'static,
+    {
+        self.validators
+            .push(Validator::with_message(checksum, "checksum failed"));
+        self
     }
 
-    /// Consumes the `Input` and applies the given function to it.
+    /// Set what is considered empty input.
     ///
-    /// This function uses [`FromStr`] to parse the input data. The result is
-    /// then fed to the given closure.
+    /// If set, this function will be called on the trimmed user input to
+    /// determine whether it is empty. Empty input results in the default
+    /// value being returned, if set. By default only input consisting
+    /// entirely of whitespace is considered empty.
+    ///
+    /// # Examples
     ///
     /// ```no_run
     /// # use casual::Input;
-    /// let value = Input::new().map(|s: String| &s.to_lowercase() == "yes");
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a number or '-' to skip: ")
+    ///     .default(0)
+    ///     .empty_when(|s| s.is_empty() || s == "-")
+    ///     .get();
     /// ```
-    ///
-    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
-    pub fn map<F, U>(self, map: F) -> U
+    pub fn empty_when<F>(mut self, empty: F) -> Self
     where
-        F: Fn(T) -> U,
+        F: Fn(&str) -> bool + 'static,
     {
-        map(self.get())
+        self.text.empty = Some(Box::new(empty));
+        self
     }
-}
 
-/////////////////////////////////////////////////////////////////////////
-// Shortcut functions
-/////////////////////////////////////////////////////////////////////////
+    /// Set whether the input is trimmed.
+    ///
+    /// By default leading and trailing whitespace is removed from the input
+    /// before it is checked for emptiness, parsed, and validated. If disabled
+    /// only the line ending is removed, so only a truly empty line results in
+    /// the default value being returned and input like `" "` is parsed as
+    /// is. For [multiline](#method.multiline) input only the final line ending
+    /// is removed by default, so that indentation is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let password: String = Input::new()
+    ///     .prompt("Password: ")
+    ///     .trim(false)
+    ///     .get_from_rw(&b" secret \n"[..], Vec::new())?;
+    /// assert_eq!(password, " secret ");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.text.trim = Some(match trim {
+            true => Trim::Whitespace,
+            false => Trim::Off,
+        });
+        self
+    }
+
+    /// Trim the given characters from the input instead of whitespace.
+    ///
+    /// This is the same as [`.trim(true)`](#method.trim) except that any of
+    /// the characters in `chars` are removed from the start and end of the
+    /// input instead of whitespace. The line ending is always removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let path: String = Input::new()
+    ///     .prompt("Path: ")
+    ///     .trim_matches(" /")
+    ///     .get_from_rw(&b"/usr/local/\n"[..], Vec::new())?;
+    /// assert_eq!(path, "usr/local");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn trim_matches<S: Into<String>>(mut self, chars: S) -> Self {
+        self.text.trim = Some(Trim::Chars(chars.into().chars().collect()));
+        self
+    }
+
+    /// Reject input that consists only of whitespace.
+    ///
+    /// By default input that is only whitespace is treated the same as empty
+    /// input. If enabled, only truly empty input is considered empty and
+    /// whitespace-only input displays an error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new()
+    ///     .prompt("Name [anonymous]: ")
+    ///     .default("anonymous".to_string())
+    ///     .whitespace_is_invalid()
+    ///     .get();
+    /// ```
+    pub fn whitespace_is_invalid(mut self) -> Self {
+        self.text.whitespace_is_invalid = true;
+        self
+    }
+
+    /// Return an error for empty input when there is no default.
+    ///
+    /// By default empty input is ignored and the user is asked again. If
+    /// enabled, [`try_get()`](#method.try_get) instead returns
+    /// [`CasualError::Empty`](enum.CasualError.html#variant.Empty), which lets
+    /// non-interactive callers detect that nothing was provided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::{CasualError, Input};
+    /// let mut transcript = Vec::new();
+    /// let result = Input::<String>::new()
+    ///     .prompt("Token: ")
+    ///     .empty_is_error(true)
+    ///     .get_from_rw(&b"\n"[..], &mut transcript);
+    /// assert!(matches!(result, Err(CasualError::Empty)));
+    /// ```
+    pub fn empty_is_error(mut self, on: bool) -> Self {
+        self.text.empty_is_error = on;
+        self
+    }
+
+    /// Warn when the user enters a deprecated value.
+    ///
+    /// If the user input is exactly `old` a warning suggesting `suggestion`
+    /// is written to stderr, the input is still accepted. This can be called
+    /// multiple times to deprecate multiple values.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let format: String = Input::new()
+    ///     .prompt("Output format: ")
+    ///     .deprecate("yml", "yaml")
+    ///     .get();
+    /// ```
+    pub fn deprecate<S, U>(mut self, old: S, suggestion: U) -> Self
+    where
+        S: Into<String>,
+        U: Into<String>,
+    {
+        self.text.deprecated.push((old.into(), suggestion.into()));
+        self
+    }
+
+    /// Accept English number words in place of digits.
+    ///
+    /// If enabled, input like "five" or "twenty-one" is converted to digits
+    /// before it is parsed. Words from zero to ninety-nine are recognized,
+    /// anything else is parsed as normal. This is intended for integer types.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let num: u32 = Input::new()
+    ///     .prompt("How many? ")
+    ///     .accept_number_words()
+    ///     .get();
+    /// ```
+    pub fn accept_number_words(mut self) -> Self {
+        self.text.number_words = true;
+        self
+    }
+
+    /// Accept floating point input by rounding it to the nearest integer.
+    ///
+    /// If enabled, input that fails to parse but is a valid float, like
+    /// "3.0", is rounded and parsed again. If the rounded value is out of
+    /// range for the type an error is displayed. This is intended for integer
+    /// types.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a whole number: ")
+    ///     .round_floats()
+    ///     .get();
+    /// ```
+    pub fn round_floats(mut self) -> Self {
+        self.text.round_floats = true;
+        self
+    }
+
+    /// Evaluate simple arithmetic expressions.
+    ///
+    /// If enabled, input that fails to parse but contains an arithmetic
+    /// expression like `1024 * 8` is evaluated and the result is parsed
+    /// instead. Addition, subtraction, multiplication, division, and
+    /// parentheses are supported. This is intended for numeric types.
+    ///
+    /// Numbers must be written out in full, so an expression using scientific
+    /// notation like `1e3+1` is rejected. The result is formatted and parsed
+    /// like any other input, so if it is not a whole number and `T` is an
+    /// integer type the usual parse error for `T` is displayed.
+    ///
+    /// This requires the `arithmetic` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let bytes: u64 = Input::new()
+    ///     .prompt("Buffer size in bytes: ")
+    ///     .eval_arithmetic()
+    ///     .get_from_rw(&b"7 / 2\n1024 * 8\n"[..], &mut transcript)?;
+    /// assert_eq!(bytes, 8192);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Buffer size in bytes: Error: invalid digit found in string\nBuffer size in bytes: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    #[cfg(feature = "arithmetic")]
+    pub fn eval_arithmetic(mut self) -> Self {
+        self.text.arithmetic = true;
+        self
+    }
+
+    /// Transform the input before it is parsed.
+    ///
+    /// The given function is applied to the trimmed user input, for example
+    /// to remove currency symbols or thousands separators. Empty input is
+    /// detected before this, so it still results in the default. This can be
+    /// called multiple times, in which case the functions are applied in the
+    /// order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let price: f64 = Input::new()
+    ///     .prompt("Price: ")
+    ///     .transform_raw(|s| s.trim_start_matches('$').replace(',', ""))
+    ///     .get_from_rw(&b"$1,299.99\n"[..], &mut transcript)?;
+    /// assert_eq!(price, 1299.99);
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn transform_raw<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.text.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Transform the input, showing a live preview of the result.
+    ///
+    /// The given function is applied to the trimmed user input before it is
+    /// parsed. If stdin and stdout are a terminal the transformed input is
+    /// displayed on the line below the prompt and updated as the user types.
+    /// Otherwise, or if the terminal can not be put into raw mode, the input
+    /// is read as normal. Raw mode is currently only supported on Unix.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let slug: String = Input::new()
+    ///     .prompt("Title: ")
+    ///     .live_preview(|s| s.to_lowercase().replace(' ', "-"))
+    ///     .get();
+    /// ```
+    pub fn live_preview<F>(mut self, preview: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.text.preview = Some(Rc::new(preview));
+        self
+    }
+
+    /// Validate a folded version of the input but return the original.
+    ///
+    /// The given function is applied to the user input and the result is
+    /// parsed and passed to the validator instead. The value returned is
+    /// still parsed from what the user actually entered. This is useful for
+    /// things like case insensitive comparisons.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let taken = vec!["alice".to_string(), "bob".to_string()];
+    /// let username: String = Input::new()
+    ///     .prompt("Username: ")
+    ///     .matches(move |s| !taken.contains(s))
+    ///     .fold_for_validation(|s| s.to_lowercase())
+    ///     .get();
+    /// ```
+    pub fn fold_for_validation<F>(mut self, fold: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.text.fold = Some(Box::new(fold));
+        self
+    }
+
+    /// Only accept input that is one of the options returned by the given
+    /// function.
+    ///
+    /// The function is called once when the input is read, not when this
+    /// method is called, so the options can depend on state that is only
+    /// known later. The input is compared to each option before it is parsed
+    /// and the options are listed if it does not match any of them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// # fn discover_plugins() -> Vec<String> { Vec::new() }
+    /// let plugin: String = Input::new()
+    ///     .prompt("Plugin: ")
+    ///     .options_fn(discover_plugins)
+    ///     .get();
+    /// ```
+    pub fn options_fn<F>(mut self, options: F) -> Self
+    where
+        F: Fn() -> Vec<String> + 'static,
+    {
+        self.text.options = Some(Box::new(options));
+        self
+    }
+
+    /// Run a function once, just before the prompt is first displayed.
+    ///
+    /// This is useful for one-time setup like printing a header. Unlike the
+    /// prompt, the function is not run again if the input is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new()
+    ///     .prompt("Name: ")
+    ///     .on_start(|| println!("== Account setup =="))
+    ///     .get();
+    /// ```
+    pub fn on_start<F>(mut self, start: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        self.view.start = Some(Box::new(start));
+        self
+    }
+
+    /// Display error messages using the given function.
+    ///
+    /// The function is called with the error message whenever the input is
+    /// invalid, instead of writing `Error: ` and the message. This is useful
+    /// for styling or logging errors. It is called before the prompt is
+    /// displayed again, and any help message is still displayed after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let errors = Rc::new(RefCell::new(Vec::new()));
+    /// let log = errors.clone();
+    /// let mut transcript = Vec::new();
+    /// let port: u16 = Input::new()
+    ///     .prompt("Port: ")
+    ///     .on_error(move |msg| log.borrow_mut().push(msg.to_string()))
+    ///     .get_from_rw(&b"http\n8080\n"[..], &mut transcript)?;
+    /// assert_eq!(port, 8080);
+    /// assert_eq!(*errors.borrow(), ["invalid digit found in string"]);
+    /// assert_eq!(String::from_utf8(transcript).unwrap(), "Port: Port: ");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: Fn(&str) + 'static,
+    {
+        self.view.on_error = Some(Box::new(on_error));
+        self
+    }
+
+    /// Style the prompt using the given function.
+    ///
+    /// The function is called with the whole prompt, including the prefix and
+    /// suffix, and returns the text to display instead. This is intended for
+    /// wrapping the prompt in ANSI escape codes without this crate depending
+    /// on a color crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let name: String = Input::new()
+    ///     .prompt("Name: ")
+    ///     .prompt_style(|s| format!("\x1b[1m{}\x1b[0m", s))
+    ///     .get_from_rw(&b"Ferris\n"[..], &mut transcript)?;
+    /// assert_eq!(String::from_utf8(transcript).unwrap(), "\x1b[1mName: \x1b[0m");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn prompt_style<F>(mut self, style: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.view.prompt_style = Some(Box::new(style));
+        self
+    }
+
+    /// Style the `Error:` label using the given function.
+    ///
+    /// The function is called with the label and returns the text to display
+    /// instead, the error message itself is not styled. See
+    /// [`.on_error()`](#method.on_error) to change how the whole error is
+    /// displayed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let age: u32 = Input::new()
+    ///     .prompt("Age: ")
+    ///     .error_style(|s| format!("\x1b[31m{}\x1b[0m", s))
+    ///     .get_from_rw(&b"old\n42\n"[..], &mut transcript)?;
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Age: \x1b[31mError:\x1b[0m invalid digit found in string\nAge: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn error_style<F>(mut self, style: F) -> Self
+    where
+        F: Fn(&str) -> String + 'static,
+    {
+        self.view.error_style = Some(Box::new(style));
+        self
+    }
+
+    /// Warn when the input is longer than `soft` characters and reject it
+    /// when it is longer than `hard` characters.
+    ///
+    /// The length is the number of characters in the trimmed input. Input
+    /// longer than `soft` is still accepted but a warning is written to
+    /// stderr.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let summary: String = Input::new()
+    ///     .prompt("Summary: ")
+    ///     .length_limits(50, 72)
+    ///     .get();
+    /// ```
+    pub fn length_limits(mut self, soft: usize, hard: usize) -> Self {
+        self.text.length_limits = Some((soft, hard));
+        self
+    }
+
+    /// Offer to correct common typos.
+    ///
+    /// If the user input contains one of the typos the user is asked whether
+    /// they meant the input with the typo replaced by its correction, like
+    /// [`confirm`](fn.confirm.html). If they answer yes the corrected input
+    /// is used instead. Only the first typo found is corrected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let email: String = Input::new()
+    ///     .prompt("Email: ")
+    ///     .suggest_corrections(&[("gmial.com", "gmail.com"), ("yaho.com", "yahoo.com")])
+    ///     .get_from_rw(&b"ferris@gmial.com\ny\n"[..], &mut transcript)?;
+    /// assert_eq!(email, "ferris@gmail.com");
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Email: Did you mean `ferris@gmail.com`? [y/N] "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn suggest_corrections(mut self, corrections: &[(&str, &str)]) -> Self {
+        self.text.corrections.extend(
+            corrections
+                .iter()
+                .map(|(typo, fix)| (typo.to_string(), fix.to_string())),
+        );
+        self
+    }
+
+    /// Cancel the input if the user enters one of the given words.
+    ///
+    /// The input is compared to each word before it is parsed, so the words
+    /// don't need to be valid values. If it matches,
+    /// [`try_get()`](#method.try_get) returns
+    /// [`CasualError::Cancelled`](enum.CasualError.html#variant.Cancelled)
+    /// and [`get_opt()`](#method.get_opt) returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::{CasualError, Input};
+    /// let mut transcript = Vec::new();
+    /// let result = Input::<u32>::new()
+    ///     .prompt("Quantity (or q to cancel): ")
+    ///     .abort_on(&["q", "quit"])
+    ///     .get_from_rw(&b"q\n"[..], &mut transcript);
+    /// assert!(matches!(result, Err(CasualError::Cancelled)));
+    /// ```
+    pub fn abort_on(mut self, words: &[&str]) -> Self {
+        self.text
+            .abort
+            .extend(words.iter().map(|word| word.to_string()));
+        self
+    }
+
+    /// Display lines of context above the prompt.
+    ///
+    /// The lines are displayed before the prompt, for example to show the
+    /// current settings or some examples. If stdout is a terminal they are
+    /// redrawn in place each time the user is prompted again, along with the
+    /// last error, so that the screen stays tidy. Otherwise they are only
+    /// displayed once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let cron: String = Input::new()
+    ///     .prompt("Schedule: ")
+    ///     .context_lines(vec![
+    ///         "Enter a cron expression, for example:".to_string(),
+    ///         "  0 * * * *   every hour".to_string(),
+    ///         "  0 0 * * 1   every Monday".to_string(),
+    ///     ])
+    ///     .get();
+    /// ```
+    pub fn context_lines(mut self, lines: Vec<String>) -> Self {
+        self.view.context = lines;
+        self
+    }
+
+    /// Mark the input as required.
+    ///
+    /// Empty input will never be accepted, even if a default is set, and the
+    /// prompt will be annotated with a marker. The default marker is `* ` and
+    /// it is displayed after the prefix and before the prompt.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new().prompt("Name: ").required().get();
+    /// ```
+    pub fn required(mut self) -> Self {
+        self.view.required.get_or_insert_with(|| "* ".to_string());
+        self
+    }
+
+    /// Mark the input as required using a custom marker.
+    ///
+    /// This is the same as [`.required()`](#method.required) except that the
+    /// given marker is displayed instead of `* `.
+    pub fn required_marker<S: Into<String>>(mut self, marker: S) -> Self {
+        self.view.required = Some(marker.into());
+        self
+    }
+
+    /// Display increasingly detailed help each time the input is invalid.
+    ///
+    /// After the first invalid input the first help message is displayed
+    /// below the error, after the second the second message, and so on. Once
+    /// the messages run out the last one is repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let port: u16 = Input::new()
+    ///     .prompt("Port: ")
+    ///     .help_levels(vec![
+    ///         "Enter a port number.".to_string(),
+    ///         "Enter a whole number between 0 and 65535, for example 8080.".to_string(),
+    ///     ])
+    ///     .get();
+    /// ```
+    pub fn help_levels(mut self, help: Vec<String>) -> Self {
+        self.view.help = help;
+        self
+    }
+
+    /// Limit how many times the user can enter invalid input.
+    ///
+    /// Every input that is not accepted counts as an attempt, including
+    /// empty input when there is no default. Once `attempts` inputs have
+    /// been rejected [`.try_get()`](#method.try_get) returns
+    /// [`CasualError::TooManyAttempts`](enum.CasualError.html#variant.TooManyAttempts)
+    /// and [`.get()`](#method.get) panics. The count starts from zero each
+    /// time the input is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::{CasualError, Input};
+    /// let result = Input::<u32>::new()
+    ///     .prompt("Enter a number: ")
+    ///     .max_attempts(2)
+    ///     .get_from_rw(&b"one\ntwo\n3\n"[..], Vec::new());
+    /// assert!(matches!(result, Err(CasualError::TooManyAttempts)));
+    /// ```
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.read.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Read multiple lines, until a line that is exactly `terminator`.
+    ///
+    /// The lines before the terminator are joined with `\n`, whatever line
+    /// ending was entered, and parsed and validated as a whole. Unless
+    /// [trimming](#method.trim) is set only the final line ending is removed.
+    /// A hint saying how to end the input is displayed after the prompt. If
+    /// the end of input is reached before the terminator the lines read so
+    /// far are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let message: String = Input::new()
+    ///     .prompt("Message: ")
+    ///     .multiline_until("END")
+    ///     .get_from_rw(&b"Hello\nWorld\nEND\n"[..], Vec::new())?;
+    /// assert_eq!(message, "Hello\nWorld");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn multiline_until<S: Into<String>>(self, terminator: S) -> Self {
+        self.multiline(Some(terminator.into()))
+    }
+
+    /// Read multiple lines, until a line that is exactly `terminator` or the
+    /// end of input.
+    ///
+    /// This is the same as [`.multiline_until()`](#method.multiline_until)
+    /// except that the terminator is optional. If it is `None` lines are read
+    /// until the end of input, for example when the user presses Ctrl-D.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let message: String = Input::new()
+    ///     .prompt("Commit message: ")
+    ///     .multiline(None)
+    ///     .get_from_rw(&b"Fix typo\n\nIn the readme.\n"[..], Vec::new())?;
+    /// assert_eq!(message, "Fix typo\n\nIn the readme.");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn multiline(mut self, terminator: Option<String>) -> Self {
+        self.read.multiline = true;
+        self.read.terminator = terminator;
+        self
+    }
+
+    /// Check each line of [multiline](#method.multiline) input as it is
+    /// read.
+    ///
+    /// If `validate` returns an error for a line then reading stops straight
+    /// away, instead of after the rest of the block. The error is displayed
+    /// along with the line number and the user is asked to enter the block
+    /// again. This can be called multiple times. It has no effect unless
+    /// multiline input is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let rows: String = Input::new()
+    ///     .prompt("Rows: ")
+    ///     .multiline_until(".")
+    ///     .validate_lines(|line| match line.split(',').count() {
+    ///         3 => Ok(()),
+    ///         n => Err(format!("expected 3 columns, found {}", n)),
+    ///     })
+    ///     .get_from_rw(&b"a,b,c\nd,e\nd,e,f\n.\n"[..], &mut transcript)?;
+    /// assert_eq!(rows, "d,e,f");
+    /// assert!(String::from_utf8(transcript)
+    ///     .unwrap()
+    ///     .contains("Error: line 2: expected 3 columns, found 2\n"));
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn validate_lines<F>(mut self, validate: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.read.line_validators.push(Box::new(validate));
+        self
+    }
+
+    /// Limit how many bytes of input will be accepted for a single line.
+    ///
+    /// If the user enters a line longer than `limit` bytes, not including the
+    /// newline, the rest of the line is discarded and an error is returned.
+    /// This guards against unbounded memory use when reading untrusted input.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new()
+    ///     .prompt("Please enter your name: ")
+    ///     .read_limit(256)
+    ///     .get();
+    /// ```
+    pub fn read_limit(mut self, limit: usize) -> Self {
+        self.read.limit = Some(ReadLimit {
+            bytes: limit,
+            truncate: false,
+        });
+        self
+    }
+
+    /// Limit how many bytes of input will be accepted for a single line,
+    /// truncating anything longer.
+    ///
+    /// This is the same as [`.read_limit()`](#method.read_limit) except that
+    /// lines longer than `limit` bytes are cut short instead of causing an
+    /// error. The rest of the line is discarded.
+    pub fn read_limit_truncate(mut self, limit: usize) -> Self {
+        self.read.limit = Some(ReadLimit {
+            bytes: limit,
+            truncate: true,
+        });
+        self
+    }
+
+    /// Use another `Input` if the user abandons this one.
+    ///
+    /// If this input is [cancelled](enum.CasualError.html#variant.Cancelled),
+    /// for example using one of the words given to
+    /// [`.abort_on()`](#method.abort_on), or if it [times out](#method.timeout)
+    /// without a default, then `other` is used to read the value instead. Any
+    /// other error, like reaching the end of input or a line longer than the
+    /// [read limit](#method.read_limit), is returned as normal. Fallbacks can
+    /// be chained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let lines = b"skip\nHubert\n";
+    /// let mut transcript = Vec::new();
+    /// let name: String = Input::new()
+    ///     .prompt("Full name: ")
+    ///     .abort_on(&["skip"])
+    ///     .or_else(Input::new().prompt("Nickname: "))
+    ///     .get_from_rw(&lines[..], &mut transcript)?;
+    /// assert_eq!(name, "Hubert");
+    /// assert_eq!(String::from_utf8(transcript).unwrap(), "Full name: Nickname: ");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn or_else(mut self, other: Input<T>) -> Self {
+        let fallback = match self.fallback.take() {
+            Some(fallback) => fallback.or_else(other),
+            None => other,
+        };
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Write the prompt and any errors to stderr instead of stdout.
+    ///
+    /// This keeps stdout clean when the output of the program is piped
+    /// somewhere else, for example in `value=$(mytool)`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new()
+    ///     .prompt("Name: ")
+    ///     .to_stderr(true)
+    ///     .get();
+    /// println!("{}", name);
+    /// ```
+    pub fn to_stderr(mut self, on: bool) -> Self {
+        self.view.stderr = on;
+        self
+    }
+
+    /// Don't display what the user types, for example when reading a
+    /// password.
+    ///
+    /// If stdin is a terminal it is put into raw mode while reading so that
+    /// input is not echoed. If the terminal can not be put into raw mode an
+    /// error is returned rather than displaying the input. If stdin is not a
+    /// terminal, for example when input is piped, the input is read as
+    /// normal. The input is still parsed and validated as normal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let password: String = Input::new()
+    ///     .prompt("Password: ")
+    ///     .secret(true)
+    ///     .get();
+    /// ```
+    pub fn secret(mut self, secret: bool) -> Self {
+        self.read.secret = secret;
+        self
+    }
+
+    /// Display each character the user types as the given character, for
+    /// example when reading a password.
+    ///
+    /// This is the same as [`.secret()`](#method.secret) except that the mask
+    /// character is displayed in place of the input, so the user can see how
+    /// much they have typed. Like `.secret()`, if stdin is a terminal that can
+    /// not be put into raw mode an error is returned rather than displaying
+    /// the input, and if stdin is not a terminal the input is read as normal.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let password: String = Input::new()
+    ///     .prompt("Password: ")
+    ///     .mask('*')
+    ///     .get();
+    /// ```
+    pub fn mask(mut self, mask: char) -> Self {
+        self.read.secret = true;
+        self.read.mask = Some(mask);
+        self
+    }
+
+    /// Re-display the prompt if the user has not responded in a while.
+    ///
+    /// While waiting for input the prompt will be written again on a new line
+    /// every `interval`. Anything the user has already typed is not lost.
+    ///
+    /// The wait happens on a background thread so this works on all
+    /// platforms. Reminders are only written to a terminal, so this has no
+    /// effect if no prompt is set, or when stdin or the stream the prompt is
+    /// written to is not a terminal. This is stdout, or stderr if
+    /// [`.to_stderr()`](#method.to_stderr) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// use std::time::Duration;
+    ///
+    /// let name: String = Input::new()
+    ///     .prompt("Please enter your name: ")
+    ///     .remind_every(Duration::from_secs(60))
+    ///     .get();
+    /// ```
+    pub fn remind_every(mut self, interval: Duration) -> Self {
+        self.read.remind = Some(interval);
+        self
+    }
+
+    /// Give up waiting for input after the given duration.
+    ///
+    /// If the user has not entered a line within `timeout` the default value
+    /// is returned, if set, otherwise [`try_get()`](#method.try_get) returns
+    /// an I/O error of kind `TimedOut`. The timeout starts again each time
+    /// the prompt is displayed after invalid input.
+    ///
+    /// Like [`.remind_every()`](#method.remind_every) the wait happens on a
+    /// background thread, so this also works when stdin is not a terminal.
+    /// The thread keeps waiting after the timeout and the line it reads is
+    /// returned by the next read from stdin made by this crate. Reading stdin
+    /// directly in the meantime blocks until that line is entered, and does
+    /// not see it. The timeout is ignored for [secret](#method.secret) input,
+    /// input with a preview, and when reading from a custom reader.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// use std::time::Duration;
+    ///
+    /// let language: String = Input::new()
+    ///     .prompt("Language: ")
+    ///     .default("en".to_string())
+    ///     .timeout(Duration::from_secs(30))
+    ///     .get();
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.read.timeout = Some(timeout);
+        self
+    }
+
+    /// Display the time left before the [timeout](#method.timeout) in front
+    /// of the prompt.
+    ///
+    /// The line is redrawn every second with a carriage return, for example
+    /// `Proceeding in 9s... Continue? [y/N] `. This replaces any
+    /// [reminder](#method.remind_every). Nothing extra is displayed when no
+    /// timeout is set, or when stdin or the stream the prompt is written to
+    /// is not a terminal. This is stdout, or stderr if
+    /// [`.to_stderr()`](#method.to_stderr) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// use std::time::Duration;
+    ///
+    /// let proceed: bool = Input::new()
+    ///     .prompt("Continue? [y/N] ")
+    ///     .default(false)
+    ///     .timeout(Duration::from_secs(10))
+    ///     .countdown_display()
+    ///     .get();
+    /// ```
+    pub fn countdown_display(mut self) -> Self {
+        self.read.countdown = true;
+        self
+    }
+}
+
+impl<T: 'static> Input<T> {
+    /// Construct another type from the input value.
+    ///
+    /// The input is parsed and validated as normal and then passed to
+    /// `construct`. If it returns an error the error is displayed and the
+    /// user is prompted again. This is useful for types with a validating
+    /// constructor. Any default that is already set is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// struct Email(String);
+    ///
+    /// impl Email {
+    ///     fn new(s: String) -> Result<Self, String> {
+    ///         match s.contains('@') {
+    ///             true => Ok(Self(s)),
+    ///             false => Err(format!("`{}` is not an email address", s)),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let email = Input::<String>::new()
+    ///     .prompt("Email: ")
+    ///     .construct(Email::new)
+    ///     .get();
+    /// ```
+    pub fn construct<U, E, F>(self, construct: F) -> Input<U>
+    where
+        E: Display,
+        F: Fn(T) -> Result<U, E> + 'static,
+    {
+        self.map_parser(|parser, validators| {
+            Parser::new(move |s| {
+                let value = parser.run(s)?;
+                for validator in &validators {
+                    validator.run(&value)?;
+                }
+                construct(value).map_err(|err| err.to_string())
+            })
+        })
+    }
+
+    /// Convert the input value to another type.
+    ///
+    /// The input is parsed and validated as normal and then passed to `map`.
+    /// Validators added after this work on the converted value. Any default
+    /// that is already set is discarded. Unlike [`.map()`](#method.map) this
+    /// does not read the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let name = Input::<String>::new()
+    ///     .prompt("Name: ")
+    ///     .map_value(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+    ///     .matches(|s| s.len() <= 10)
+    ///     .get_from_rw(&b"Jane    Doe\n"[..], Vec::new())?;
+    /// assert_eq!(name, "Jane Doe");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn map_value<U, F>(self, map: F) -> Input<U>
+    where
+        F: Fn(T) -> U + 'static,
+    {
+        self.construct(move |value| Ok::<_, Infallible>(map(value)))
+    }
+
+    /// Read a list of values separated by `sep`.
+    ///
+    /// The input is split on `sep` and each element is trimmed, parsed, and
+    /// validated on its own. Empty elements are ignored. If any element is
+    /// invalid the error says which one and the user is prompted again. Any
+    /// default that is already set is discarded, set a default on the list
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let ports = Input::<u16>::new()
+    ///     .prompt("Ports: ")
+    ///     .list(',')
+    ///     .default(Vec::new())
+    ///     .get_from_rw(&b"80, http\n80, 443\n"[..], &mut transcript)?;
+    /// assert_eq!(ports, [80, 443]);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Ports: Error: invalid element `http`: invalid digit found in string\nPorts: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn list(self, sep: char) -> Input<Vec<T>> {
+        self.map_parser(move |parser, validators| {
+            let element = move |token: &str| -> Result<T, String> {
+                let value = parser.run(token)?;
+                for validator in &validators {
+                    validator.run(&value)?;
+                }
+                Ok(value)
+            };
+            Parser::new(move |s| {
+                s.split(sep)
+                    .map(str::trim)
+                    .filter(|token| !token.is_empty())
+                    .map(|token| {
+                        element(token)
+                            .map_err(|err| format!("invalid element `{}`: {}", token, err))
+                    })
+                    .collect()
+            })
+        })
+    }
+}
+
+impl<T: Display> Input<T> {
+    /// Display the default value in the prompt.
+    ///
+    /// If enabled and a default is set, it is displayed in square brackets
+    /// after the prompt and before the suffix. If there is no suffix it is
+    /// displayed before any trailing punctuation in the prompt, so a prompt
+    /// of `Enter a number: ` with a default of `0` is displayed as
+    /// `Enter a number [0]: `.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a number: ")
+    ///     .default(0)
+    ///     .show_default(true)
+    ///     .get_from_rw(&b"\n"[..], &mut transcript)?;
+    /// assert_eq!(num, 0);
+    /// assert_eq!(transcript, b"Enter a number [0]: ");
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn show_default(mut self, show: bool) -> Self {
+        self.show_default = match show {
+            true => Some(|value| value.to_string()),
+            false => None,
+        };
+        self
+    }
+}
+
+#[cfg(feature = "sticky")]
+impl<T> Input<T>
+where
+    T: FromStr + Display + 'static,
+{
+    /// Remember the value between runs, using it as the next default.
+    ///
+    /// The last value accepted for `key` is loaded and used as the default,
+    /// replacing any default that is already set. When a value is accepted it
+    /// is saved for next time in the user's cache directory. If there is no
+    /// saved value, or it can't be parsed, the default is left as is. Errors
+    /// saving the value are ignored.
+    ///
+    /// This requires the `sticky` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let region: String = Input::new()
+    ///     .prompt("Region: ")
+    ///     .default("us-east-1".to_string())
+    ///     .sticky("region")
+    ///     .get();
+    /// ```
+    pub fn sticky(mut self, key: &str) -> Self {
+        if let Some(value) = sticky::load(key).and_then(|value| value.parse().ok()) {
+            self.default = Some(value);
+            self.default_with = None;
+        }
+        let key = key.to_string();
+        self.save = Some(Box::new(move |value| {
+            sticky::save(&key, &value.to_string())
+        }));
+        self
+    }
+}
+
+impl<T: Debug> Input<T> {
+    /// Print the parsed value before returning it.
+    ///
+    /// After the input is successfully parsed its `Debug` representation is
+    /// written to stderr. This is intended as a development aid to check how
+    /// a type's `FromStr` implementation interprets input.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// use std::net::IpAddr;
+    ///
+    /// let addr: IpAddr = Input::new()
+    ///     .prompt("Address: ")
+    ///     .echo_debug()
+    ///     .get();
+    /// ```
+    pub fn echo_debug(mut self) -> Self {
+        self.debug = Some(|value| format!("{:?}", value));
+        self
+    }
+}
+
+impl<T> Input<T>
+where
+    T: Copy + PartialOrd + Display + Into<f64> + 'static,
+{
+    /// Only accept values in the given range, displaying a bar that shows
+    /// where the value lies in the range.
+    ///
+    /// If stdin and stdout are a terminal the bar is displayed beside the
+    /// input and updated as the user types, for example `[####------] 40`.
+    /// Otherwise the input is read as normal. In both cases values outside of
+    /// the range display an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let volume: u8 = Input::new()
+    ///     .prompt("Volume: ")
+    ///     .bar_display(0..=100)
+    ///     .get();
+    /// ```
+    pub fn bar_display(mut self, range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        let (low, high) = (start.into(), end.into());
+        self.text.display = Some(Rc::new(move |s| {
+            const WIDTH: usize = 10;
+            match s.parse::<f64>() {
+                Ok(value) if value.is_finite() => {
+                    let fraction = match high > low {
+                        true => ((value - low) / (high - low)).clamp(0.0, 1.0),
+                        false => 1.0,
+                    };
+                    let filled = (fraction * WIDTH as f64).round() as usize;
+                    format!(
+                        "[{}{}] {}",
+                        "#".repeat(filled),
+                        "-".repeat(WIDTH - filled),
+                        s
+                    )
+                }
+                _ => format!("[{}]", "-".repeat(WIDTH)),
+            }
+        }));
+        self.validators.push(Validator::new(move |value: &T| {
+            if *value < start || *value > end {
+                return Err(format!("must be between {} and {}", start, end));
+            }
+            Ok(())
+        }));
+        self
+    }
+}
+
+impl<T> Input<T>
+where
+    T: Copy + Default + PartialOrd + Sub<Output = T> + Display + 'static,
+{
+    /// Only accept values that fit in a shared budget, subtracting accepted
+    /// values from it.
+    ///
+    /// The remaining budget is displayed before the prompt. Input that is
+    /// negative or greater than the remaining budget displays an error. The
+    /// accepted value, including the default, is then subtracted from the
+    /// budget so that the next input using the same budget sees what is left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    ///
+    /// let points = Rc::new(Cell::new(10));
+    /// let mut transcript = Vec::new();
+    /// let strength: u32 = Input::new()
+    ///     .prompt("Strength: ")
+    ///     .budget(points.clone())
+    ///     .get_from_rw(&b"12\n7\n"[..], &mut transcript)?;
+    /// assert_eq!(strength, 7);
+    /// assert_eq!(points.get(), 3);
+    /// assert!(String::from_utf8(transcript)
+    ///     .unwrap()
+    ///     .starts_with("[remaining: 10] Strength: Error:"));
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn budget(mut self, budget: Rc<Cell<T>>) -> Self {
+        let remaining = budget.clone();
+        let spend = budget.clone();
+        self.validators.push(Validator::new(move |value: &T| {
+            let remaining = budget.get();
+            if *value < T::default() {
+                return Err("must not be negative".to_string());
+            }
+            if *value > remaining {
+                return Err(format!("exceeds the remaining budget of {}", remaining));
+            }
+            Ok(())
+        }));
+        self.budget = Some(Budget {
+            remaining: Box::new(move || remaining.get().to_string()),
+            spend: Box::new(move |value| {
+                // a default is not validated so it might be over budget
+                let remaining = spend.get();
+                match *value > remaining {
+                    true => spend.set(T::default()),
+                    false => spend.set(remaining - *value),
+                }
+            }),
+        });
+        self
+    }
+}
+
+impl<T> Input<T>
+where
+    T: Clone + PartialOrd + Display + 'static,
+{
+    /// Only accept values in the given range.
+    ///
+    /// Any kind of range can be used, for example `1..=10`, `0.0..1.0`, or
+    /// `18..`. Values outside of the range display an error describing the
+    /// range, like "must be between 1 and 10".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let num: u32 = Input::new()
+    ///     .prompt("Pick a number: ")
+    ///     .range(1..=10)
+    ///     .get_from_rw(&b"11\n7\n"[..], &mut transcript)?;
+    /// assert_eq!(num, 7);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Pick a number: Error: must be between 1 and 10\nPick a number: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn range<R: RangeBounds<T>>(mut self, range: R) -> Self {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+        let message = match (&start, &end) {
+            (Bound::Included(start), Bound::Included(end)) => {
+                format!("must be between {} and {}", start, end)
+            }
+            (start, end) => {
+                let lower = match start {
+                    Bound::Included(start) => Some(format!("at least {}", start)),
+                    Bound::Excluded(start) => Some(format!("greater than {}", start)),
+                    Bound::Unbounded => None,
+                };
+                let upper = match end {
+                    Bound::Included(end) => Some(format!("at most {}", end)),
+                    Bound::Excluded(end) => Some(format!("less than {}", end)),
+                    Bound::Unbounded => None,
+                };
+                let parts: Vec<String> = lower.into_iter().chain(upper).collect();
+                format!("must be {}", parts.join(" and "))
+            }
+        };
+        self.validators.push(Validator::new(move |value: &T| {
+            if !(start.as_ref(), end.as_ref()).contains(value) {
+                return Err(message.clone());
+            }
+            Ok(())
+        }));
+        self
+    }
+}
+
+impl<T: PartialEq + 'static> Input<T> {
+    /// Reject input equal to a placeholder value.
+    ///
+    /// If the parsed input is equal to `placeholder` an error asking the user
+    /// to replace it is displayed. Like other validation this does not apply
+    /// to the default value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name: String = Input::new()
+    ///     .prompt("Name (replace <your-name>): ")
+    ///     .reject_value("<your-name>".to_string())
+    ///     .get();
+    /// ```
+    pub fn reject_value(mut self, placeholder: T) -> Self {
+        self.validators.push(Validator::new(move |value| {
+            if *value == placeholder {
+                return Err("please replace the placeholder".to_string());
+            }
+            Ok(())
+        }));
+        self
+    }
+}
+
+impl Input<String> {
+    /// Only accept one of the given words, ignoring case.
+    ///
+    /// The input is returned as entered, so it could differ in case from the
+    /// word it matched. If it doesn't match any of the words they are listed
+    /// in the error message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::prompt;
+    /// let mut transcript = Vec::new();
+    /// let level = prompt("Level: ")
+    ///     .one_of(&["debug", "info", "warn"])
+    ///     .get_from_rw(&b"trace\nINFO\n"[..], &mut transcript)?;
+    /// assert_eq!(level, "INFO");
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Level: Error: expected one of: debug, info, warn\nLevel: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn one_of(mut self, words: &[&str]) -> Self {
+        let error = format!("expected one of: {}", words.join(", "));
+        let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+        self.validators.push(Validator::new(move |s: &String| {
+            match words.contains(&s.to_lowercase()) {
+                true => Ok(()),
+                false => Err(error.clone()),
+            }
+        }));
+        self
+    }
+
+    /// Only accept valid identifiers.
+    ///
+    /// An identifier must start with a letter or an underscore, followed by
+    /// any number of letters, digits, or underscores.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name = Input::<String>::new()
+    ///     .prompt("Variable name: ")
+    ///     .identifier()
+    ///     .get();
+    /// ```
+    pub fn identifier(self) -> Self {
+        self.identifier_with("")
+    }
+
+    /// Only accept valid identifiers, also allowing the given characters.
+    ///
+    /// This is the same as [`.identifier()`](#method.identifier) except that
+    /// the characters in `extra` are also allowed after the first character.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let name = Input::<String>::new()
+    ///     .prompt("Package name: ")
+    ///     .identifier_with("-")
+    ///     .get();
+    /// ```
+    pub fn identifier_with<S: Into<String>>(mut self, extra: S) -> Self {
+        let extra = extra.into();
+        self.validators.push(Validator::with_message(
+            move |s: &String| {
+                let mut chars = s.chars();
+                chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_alphanumeric() || c == '_' || extra.contains(c))
+            },
+            "must be a valid identifier",
+        ));
+        self
+    }
+
+    /// Parse the input using the given function instead of [`FromStr`].
+    ///
+    /// This makes it possible to read types that don't implement [`FromStr`],
+    /// or to parse them differently. If `parse` returns an error it is
+    /// displayed and the user is prompted again. Any default or validator
+    /// that is already set is discarded, set them after this instead. To
+    /// avoid starting from an `Input<String>` use
+    /// [`Input::with_parse()`](#method.with_parse).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let on = Input::<String>::new()
+    ///     .prompt("Enable? ")
+    ///     .parse_with(|s| match s {
+    ///         "on" => Ok(true),
+    ///         "off" => Ok(false),
+    ///         _ => Err("expected on or off"),
+    ///     })
+    ///     .default(false)
+    ///     .get_from_rw(&b"maybe\non\n"[..], &mut transcript)?;
+    /// assert!(on);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Enable? Error: expected on or off\nEnable? "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    pub fn parse_with<U, E, F>(self, parse: F) -> Input<U>
+    where
+        E: Display,
+        F: Fn(&str) -> Result<U, E> + 'static,
+    {
+        self.map_parser(|_, _| Parser::new(move |s| parse(s).map_err(|err| err.to_string())))
+    }
+
+    /// Parse the input using a function that reports where an error occurred.
+    ///
+    /// If `parse` fails it returns the character position of the error in
+    /// the input along with a message. A caret is displayed under that
+    /// position in the line the user entered, followed by the message, and
+    /// the user is prompted again. Any default or validator that is already
+    /// set is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let word = Input::<String>::new()
+    ///     .prompt("Lowercase word: ")
+    ///     .parse_detailed(|s| match s.chars().position(|c| !c.is_lowercase()) {
+    ///         Some(i) => Err((i, "expected a lowercase letter".to_string())),
+    ///         None => Ok(s.to_string()),
+    ///     })
+    ///     .get();
+    /// ```
+    pub fn parse_detailed<U, F>(self, parse: F) -> Input<U>
+    where
+        F: Fn(&str) -> Result<U, (usize, String)> + 'static,
+    {
+        self.map_parser(|_, _| Parser::Detailed(Box::new(parse)))
+    }
+
+    /// Append a suffix to the input if it is not already present.
+    ///
+    /// # Examples
+    ///
+    /// Typing `web1` results in `web1.example.com`.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let host = Input::<String>::new()
+    ///     .prompt("Hostname: ")
+    ///     .default_suffix(".example.com")
+    ///     .get();
+    /// ```
+    pub fn default_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        let suffix = suffix.into();
+        self.text.transforms.push(Box::new(move |s| {
+            if s.ends_with(&suffix) {
+                s.to_string()
+            } else {
+                format!("{}{}", s, suffix)
+            }
+        }));
+        self
+    }
+
+    /// Always append a suffix to the input.
+    ///
+    /// This is the same as [`.default_suffix()`](#method.default_suffix)
+    /// except that the suffix is appended even if it is already present.
+    pub fn always_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        let suffix = suffix.into();
+        self.text
+            .transforms
+            .push(Box::new(move |s| format!("{}{}", s, suffix)));
+        self
+    }
+}
+
+impl Input<char> {
+    /// Only accept characters contained in `chars`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::prompt_char;
+    /// let choice = prompt_char("Continue, retry or abort? [c/r/a] ")
+    ///     .allowed("cra")
+    ///     .get();
+    /// ```
+    pub fn allowed<S: Into<String>>(mut self, chars: S) -> Self {
+        let chars: Vec<char> = chars.into().chars().collect();
+        self.parser = Parser::new(move |s| {
+            let c = parse_char(s)?;
+            if chars.contains(&c) {
+                Ok(c)
+            } else {
+                let expected: Vec<String> = chars.iter().map(char::to_string).collect();
+                Err(format!("please enter one of: {}", expected.join(", ")))
+            }
+        });
+        self
+    }
+}
+
+fn parse_char(s: &str) -> Result<char, String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err("please enter exactly one character".to_string()),
+    }
+}
+
+/// Converts an English number word like "forty-two" to its value.
+fn number_word(s: &str) -> Option<u32> {
+    const UNITS: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 8] = [
+        "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    let s = s.to_lowercase();
+    let mut words = s
+        .split(|c: char| c == '-' || c.is_whitespace())
+        .filter(|w| !w.is_empty());
+    let position = |list: &[&str], word: &str| list.iter().position(|w| *w == word);
+    let value = match (words.next(), words.next()) {
+        (Some(word), None) => position(&UNITS, word)
+            .map(|n| n as u32)
+            .or_else(|| position(&TENS, word).map(|n| (n as u32 + 2) * 10))?,
+        (Some(tens), Some(unit)) => {
+            let tens = position(&TENS, tens)? as u32;
+            let unit = position(&UNITS[1..10], unit)? as u32;
+            (tens + 2) * 10 + unit + 1
+        }
+        _ => return None,
+    };
+    match words.next() {
+        Some(_) => None,
+        None => Some(value),
+    }
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<f64>, String> {
+    let numbers = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| format!("`{}` is not a number", token))
+        })
+        .collect::<Result<Vec<f64>, _>>()?;
+    if numbers.is_empty() {
+        return Err("please enter at least one number".to_string());
+    }
+    Ok(numbers)
+}
+
+impl<T> InputRange<T> {
+    /// Returns the start of the range.
+    pub fn start(&self) -> &T {
+        match self {
+            Self::Exclusive(range) => &range.start,
+            Self::Inclusive(range) => range.start(),
+        }
+    }
+
+    /// Returns the end of the range.
+    pub fn end(&self) -> &T {
+        match self {
+            Self::Exclusive(range) => &range.end,
+            Self::Inclusive(range) => range.end(),
+        }
+    }
+
+    /// Returns whether the range contains the given value.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialOrd,
+    {
+        RangeBounds::contains(self, value)
+    }
+
+    /// Returns whether the range contains no values.
+    pub fn is_empty(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        match self {
+            Self::Exclusive(range) => range.is_empty(),
+            Self::Inclusive(range) => range.is_empty(),
+        }
+    }
+}
+
+impl<T> RangeBounds<T> for InputRange<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(self.start())
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        match self {
+            Self::Exclusive(range) => Bound::Excluded(&range.end),
+            Self::Inclusive(range) => Bound::Included(range.end()),
+        }
+    }
+}
+
+impl Stats {
+    /// Calculate the statistics for the given non-empty list of numbers.
+    fn new(numbers: &[f64]) -> Self {
+        let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = numbers.iter().sum();
+        let mean = sum / numbers.len() as f64;
+        Self {
+            min,
+            max,
+            sum,
+            mean,
+        }
+    }
+}
+
+impl Confirm {
+    /// Construct a new `Confirm` with the given question.
+    ///
+    /// By default the answers are "yes" and "no", and the default answer is
+    /// no.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            default: false,
+            yes: "yes".to_string(),
+            no: "no".to_string(),
+        }
+    }
+
+    /// Set the answer used when the user enters an empty input.
+    ///
+    /// The first letter of the default answer is uppercased in the suffix,
+    /// for example ` [Y/n] ` if the default is yes.
+    pub fn default(mut self, default: bool) -> Self {
+        self.default = default;
+        self
+    }
+
+    /// Set the words used for yes and no.
+    ///
+    /// The user can enter either the whole word or its first letter, ignoring
+    /// case.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use casual::Confirm;
+    /// let ja = Confirm::new("Fortfahren?").words("ja", "nein").get();
+    /// ```
+    pub fn words<S, U>(mut self, yes: S, no: U) -> Self
+    where
+        S: Into<String>,
+        U: Into<String>,
+    {
+        self.yes = yes.into().to_lowercase();
+        self.no = no.into().to_lowercase();
+        self
+    }
+
+    /// Consumes the `Confirm` and asks the user the question.
+    pub fn get(self) -> bool {
+        self.input().get()
+    }
+
+    /// Returns the `Input` used to ask the question.
+    fn input(self) -> Input<bool> {
+        let Self {
+            text,
+            default,
+            yes,
+            no,
+        } = self;
+        let initial = |word: &str, upper: bool| {
+            let c = word.chars().next().unwrap_or(' ');
+            match upper {
+                true => c.to_uppercase().to_string(),
+                false => c.to_string(),
+            }
+        };
+        let suffix = format!(" [{}/{}] ", initial(&yes, default), initial(&no, !default));
+        let error = format!("please answer {} or {}", yes, no);
+        let parser = Parser::new(move |s| {
+            let answer = s.to_lowercase();
+            let matches = |word: &str| answer == word || answer == initial(word, false);
+            if matches(&yes) {
+                Ok(true)
+            } else if matches(&no) {
+                Ok(false)
+            } else {
+                Err(error.clone())
+            }
+        });
+        Input::from_parser(parser)
+            .prompt(text)
+            .suffix(suffix)
+            .default(default)
+    }
+}
+
+impl Reader {
+    /// Construct a new `Reader` that reads from stdin.
+    ///
+    /// Stdin is locked each time input is read, so the other functions in
+    /// this crate can still be used while the `Reader` is alive. Input that
+    /// was read ahead, for example a pasted block of lines, is buffered by
+    /// stdin and used by the next prompt. To hold the lock for as long as the
+    /// `Reader` is alive use
+    /// [`Reader::from_reader(io::stdin().lock())`](#method.from_reader)
+    /// instead, and only read stdin using that `Reader`.
+    pub fn new() -> Self {
+        Self {
+            reader: None,
+            min_interval: None,
+            last_read: None,
+        }
+    }
+}
+
+impl Default for Reader {
+    /// Construct a new `Reader` that reads from stdin.
+    ///
+    /// Identical to [`Reader::new()`](struct.Reader.html#method.new).
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Construct a new `Reader` that reads from the given reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Reader;
+    /// let mut reader = Reader::from_reader(&b"Ferris\n7\n"[..]);
+    /// let name: String = reader.prompt("Name: ");
+    /// let age: u32 = reader.prompt("Age: ");
+    /// assert_eq!((name.as_str(), age), ("Ferris", 7));
+    /// ```
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: Some(reader),
+            min_interval: None,
+            last_read: None,
+        }
+    }
+
+    /// Wait until at least the given duration has passed since the last
+    /// successful read before reading again.
+    ///
+    /// This stops input that was pasted or piped in all at once from being
+    /// consumed faster than the program can handle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Reader;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut reader =
+    ///     Reader::from_reader(&b"1\n2\n"[..]).min_interval(Duration::from_millis(50));
+    /// let start = Instant::now();
+    /// let values: Vec<u32> = vec![reader.prompt("> "), reader.prompt("> ")];
+    /// assert_eq!(values, [1, 2]);
+    /// assert!(start.elapsed() >= Duration::from_millis(50));
+    /// ```
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = Some(interval);
+        self
+    }
+
+    /// Prompts the user for input and reads it from this reader.
+    ///
+    /// This is the same as [`prompt(text).get()`](fn.prompt.html) except
+    /// that the input is read from this reader.
+    pub fn prompt<T, S>(&mut self, text: S) -> T
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+        S: Into<String>,
+    {
+        self.read(prompt(text)).unwrap()
+    }
+
+    /// Reads the given input from this reader.
+    ///
+    /// This is the same as [`Input::get_from()`](struct.Input.html#method.get_from)
+    /// except that the reader is not consumed, so it can be used again. When
+    /// reading from stdin this is the same as
+    /// [`Input::try_get()`](struct.Input.html#method.try_get).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::{Input, Reader};
+    /// let mut reader = Reader::from_reader(&b"200\n20\n"[..]);
+    /// let percent = reader.read(Input::<u8>::new().prompt("Percent: ").range(0..=100))?;
+    /// assert_eq!(percent, 20);
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn read<T>(&mut self, input: Input<T>) -> Result<T, CasualError> {
+        if let (Some(interval), Some(last_read)) = (self.min_interval, self.last_read) {
+            thread::sleep(interval.saturating_sub(last_read.elapsed()));
+        }
+        let value = match &mut self.reader {
+            Some(reader) => input.get_from(reader),
+            None => input.try_get(),
+        }?;
+        self.last_read = Some(Instant::now());
+        Ok(value)
+    }
+}
+
+impl<T> Lines<T> {
+    /// Skip blank lines instead of yielding an error for them.
+    pub fn skip_blank(mut self) -> Self {
+        self.skip_blank = true;
+        self
+    }
+
+    /// Check each value, yielding the returned error message as
+    /// [`CasualError::Validation`](enum.CasualError.html#variant.Validation)
+    /// if it is invalid.
+    ///
+    /// This works like
+    /// [`Input::matches_with()`](struct.Input.html#method.matches_with) and
+    /// can be called multiple times.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let ports = casual::lines::<u16>().matches_with(|port| match *port >= 1024 {
+    ///     true => Ok(()),
+    ///     false => Err(format!("port {} is reserved", port)),
+    /// });
+    /// for port in ports {
+    ///     println!("{}", port?);
+    /// }
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn matches_with<F>(mut self, matches: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        self.validators.push(Validator::new(matches));
+        self
+    }
+
+    /// Parse and validate a single non-blank line.
+    fn parse(&self, line: &str) -> Result<T, CasualError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        let value = line
+            .parse()
+            .map_err(|err: T::Err| CasualError::Parse(err.to_string()))?;
+        for validator in &self.validators {
+            validator.run(&value).map_err(CasualError::Validation)?;
+        }
+        Ok(value)
+    }
+}
+
+impl<T> Iterator for Lines<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    type Item = Result<T, CasualError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let line = match read_stdin(None) {
+                Ok(line) => line,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                }
+            };
+            if line.is_empty() {
+                self.done = true;
+                break;
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                if self.skip_blank {
+                    continue;
+                }
+                return Some(Err(CasualError::Empty));
+            }
+            return Some(self.parse(line));
+        }
+        None
+    }
+}
+
+/// A line that is being read on a background thread.
+type Pending = Mutex<Option<Receiver<io::Result<String>>>>;
+
+/// The line being read from stdin after a read with a timeout gave up
+/// waiting for it.
+static STDIN_PENDING: Pending = Mutex::new(None);
+
+/// Returns the line that is being read on a background thread, if any,
+/// waiting for it if necessary.
+fn take_pending(pending: &Pending) -> Option<io::Result<String>> {
+    let rx = pending
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()?;
+    Some(
+        rx.recv()
+            .unwrap_or_else(|_| Err(io::Error::other("stdin reader thread panicked"))),
+    )
+}
+
+/// Returns whether a line is being read from stdin on a background thread.
+fn stdin_pending() -> bool {
+    STDIN_PENDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+fn read_stdin(limit: Option<ReadLimit>) -> io::Result<String> {
+    match take_pending(&STDIN_PENDING) {
+        Some(result) => result,
+        None => read_from(&mut io::stdin().lock(), limit),
+    }
+}
+
+/// Read a line, limiting how much of it is kept.
+///
+/// Reads that are interrupted, for example by a signal, are retried by the
+/// standard library's `BufRead` methods.
+fn read_from<R: BufRead>(reader: &mut R, limit: Option<ReadLimit>) -> io::Result<String> {
+    let mut result = String::new();
+    match limit {
+        None => {
+            reader.read_line(&mut result)?;
+        }
+        Some(ReadLimit { bytes, truncate }) => {
+            let mut buf = Vec::new();
+            (&mut *reader)
+                .take(bytes as u64 + 1)
+                .read_until(b'\n', &mut buf)?;
+            if buf.len() > bytes && buf.last() != Some(&b'\n') {
+                reader.skip_until(b'\n')?;
+                if !truncate {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        LineTooLong(bytes),
+                    ));
+                }
+                buf.truncate(bytes);
+                // don't split a multi-byte character in half
+                if let Err(err) = str::from_utf8(&buf) {
+                    if err.error_len().is_none() {
+                        buf.truncate(err.valid_up_to());
+                    }
+                }
+            }
+            result = String::from_utf8(buf)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+    }
+    Ok(result)
+}
+
+fn read_line(
+    prompt: &Option<String>,
+    out: &mut dyn Write,
+    limit: Option<ReadLimit>,
+) -> io::Result<String> {
+    if let Some(prompt) = prompt {
+        out.write_all(prompt.as_bytes())?;
+        out.flush()?;
+    }
+    read_stdin(limit)
+}
+
+fn read_line_from<R: BufRead>(
+    reader: &mut R,
+    prompt: &Option<String>,
+    out: &mut dyn Write,
+    limit: Option<ReadLimit>,
+) -> io::Result<String> {
+    if let Some(prompt) = prompt {
+        out.write_all(prompt.as_bytes())?;
+        out.flush()?;
+    }
+    read_from(reader, limit)
+}
+
+/// Read a line in raw mode without displaying what is typed.
+fn read_line_secret(
+    prompt: &Option<String>,
+    out: &mut dyn Write,
+    limit: Option<ReadLimit>,
+    mask: Option<char>,
+) -> io::Result<String> {
+    // only stdin needs to be a terminal, the prompt can go anywhere
+    if !io::stdin().is_terminal() {
+        return read_line(prompt, out, limit);
+    }
+    // never fall back to displaying the input
+    let _raw = term::RawMode::enable()
+        .map_err(|err| io::Error::new(err.kind(), format!("failed to hide input: {}", err)))?;
+    if let Some(prompt) = prompt {
+        out.write_all(prompt.as_bytes())?;
+        out.flush()?;
+    }
+    // an earlier read timed out and still holds stdin, it reads the line
+    // without displaying it now that raw mode is enabled, but without a mask
+    if let Some(line) = take_pending(&STDIN_PENDING) {
+        write!(out, "\r\n")?;
+        out.flush()?;
+        return line;
+    }
+    let stdin = io::stdin();
+    // the number of mask characters displayed
+    let mut shown = 0;
+    let line = term::edit_line(&mut stdin.lock(), out, limit, |line, out| {
+        let mask = match mask {
+            Some(mask) => mask,
+            None => return Ok(()),
+        };
+        let len = line.chars().count();
+        while shown < len {
+            write!(out, "{}", mask)?;
+            shown += 1;
+        }
+        while shown > len {
+            write!(out, "\x08 \x08")?;
+            shown -= 1;
+        }
+        out.flush()
+    });
+    write!(out, "\r\n")?;
+    out.flush()?;
+    line
+}
+
+/// Read a line in raw mode, displaying a preview of it below the prompt or,
+/// if `beside` is set, to the right of the input.
+fn read_line_preview(
+    prompt: &Option<String>,
+    out: &mut dyn Write,
+    limit: Option<ReadLimit>,
+    preview: &TransformFn,
+    beside: bool,
+    interactive: bool,
+) -> io::Result<String> {
+    if !interactive || stdin_pending() {
+        return read_line(prompt, out, limit);
+    }
+    let _raw = match term::RawMode::enable() {
+        Ok(raw) => raw,
+        Err(_) => return read_line(prompt, out, limit),
+    };
+    let prompt = prompt.as_deref().unwrap_or("");
+    if !beside {
+        // reserve a line below the prompt for the preview
+        write!(out, "\n\x1b[1A")?;
+    }
+    let stdin = io::stdin();
+    let line = term::edit_line(&mut stdin.lock(), out, limit, |line, out| {
+        if beside {
+            write!(
+                out,
+                "\r\x1b[K{}{}\x1b7  {}\x1b8",
+                prompt,
+                line,
+                preview(line.trim())
+            )?;
+        } else {
+            write!(
+                out,
+                "\r\x1b[K{}{}\x1b7\x1b[1B\r\x1b[K{}\x1b8",
+                prompt,
+                line,
+                preview(line.trim())
+            )?;
+        }
+        out.flush()
+    });
+    if beside {
+        // clear the preview and move the cursor to the next line
+        write!(out, "\x1b[K\r\n")?;
+    } else {
+        // clear the preview and leave the cursor on the line below the prompt
+        write!(out, "\x1b[1B\r\x1b[K")?;
+    }
+    out.flush()?;
+    line
+}
+
+/// Display the prompt, preceded by the whole seconds left until the
+/// `countdown` deadline.
+fn write_waiting_prompt(
+    out: &mut dyn Write,
+    prompt: &Option<String>,
+    countdown: Option<Instant>,
+) -> io::Result<()> {
+    if let Some(deadline) = countdown {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        write!(out, "Proceeding in {}s... ", remaining.as_secs_f64().ceil())?;
+    }
+    if let Some(prompt) = prompt {
+        out.write_all(prompt.as_bytes())?;
+    }
+    out.flush()
+}
+
+/// Read a line on a background thread, displaying the prompt again every
+/// `interval` and giving up after `timeout`.
+///
+/// If `countdown` is set the prompt is instead redrawn every second on the
+/// same line, preceded by the seconds left before the timeout.
+///
+/// If this gives up waiting, the background thread keeps reading and its
+/// line is stored in `pending`. The next call, or `take_pending()`, waits
+/// for that line instead of calling `read` again.
+fn read_line_waiting<F>(
+    pending: &Pending,
+    read: F,
+    prompt: &Option<String>,
+    out: &mut dyn Write,
+    interval: Option<Duration>,
+    timeout: Option<Duration>,
+    countdown: bool,
+) -> io::Result<String>
+where
+    F: FnOnce() -> io::Result<String> + Send + 'static,
+{
+    let waiting = pending
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let rx = match waiting {
+        Some(rx) => rx,
+        None => {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = tx.send(read());
+            });
+            rx
+        }
+    };
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let countdown = deadline.filter(|_| countdown);
+    write_waiting_prompt(out, prompt, countdown)?;
+    loop {
+        let wait = match (countdown, interval, deadline) {
+            (Some(deadline), _, _) => {
+                // wake up when the number of whole seconds left changes
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let seconds = remaining.as_secs_f64().ceil() as u64;
+                remaining.saturating_sub(Duration::from_secs(seconds.saturating_sub(1)))
+            }
+            (None, Some(interval), Some(deadline)) => {
+                interval.min(deadline.saturating_duration_since(Instant::now()))
+            }
+            (None, Some(interval), None) => interval,
+            (None, None, Some(deadline)) => deadline.saturating_duration_since(Instant::now()),
+            (None, None, None) => Duration::MAX,
+        };
+        match rx.recv_timeout(wait) {
+            Ok(result) => break result,
+            Err(RecvTimeoutError::Timeout)
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) =>
+            {
+                *pending.lock().unwrap_or_else(PoisonError::into_inner) = Some(rx);
+                break Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "timed out waiting for input",
+                ));
+            }
+            Err(RecvTimeoutError::Timeout) if countdown.is_some() => {
+                out.write_all(b"\r\x1b[K")?;
+                write_waiting_prompt(out, prompt, countdown)?;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if let Some(prompt) = prompt {
+                    write!(out, "\n{}", prompt)?;
+                    out.flush()?;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                break Err(io::Error::other("stdin reader thread panicked"));
+            }
+        }
+    }
+}
+
+/// Read a checkbox in raw mode, returning `None` if raw mode is not
+/// available or an earlier read is still waiting for a line.
+fn read_checkbox(text: &str, default: bool) -> io::Result<Option<bool>> {
+    if !term::is_interactive() || stdin_pending() {
+        return Ok(None);
+    }
+    let _raw = match term::RawMode::enable() {
+        Ok(raw) => raw,
+        Err(_) => return Ok(None),
+    };
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let mut stdout = io::stdout();
+    let mut checked = default;
+    loop {
+        let mark = if checked { 'x' } else { ' ' };
+        write!(stdout, "\r\x1b[K[{}] {}", mark, text)?;
+        stdout.flush()?;
+        match term::read_key(&mut stdin)? {
+            term::Key::Char(' ') => checked = !checked,
+            term::Key::Enter | term::Key::Eof => break,
+            term::Key::Interrupt => {
+                writeln!(stdout, "\r")?;
+                return Err(term::interrupt());
+            }
+            _ => {}
+        }
+    }
+    write!(stdout, "\r\n")?;
+    stdout.flush()?;
+    Ok(Some(checked))
+}
+
+impl<T> Input<T> {
+    /// Returns the prompt as it is displayed, or `None` if there is no prompt.
+    ///
+    /// This includes the prefix and suffix as well as any hints added by the
+    /// other settings, like the [default](#method.show_default).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let input = Input::<u32>::new()
+    ///     .prefix("> ")
+    ///     .prompt("Enter a number")
+    ///     .suffix(": ")
+    ///     .default(7)
+    ///     .show_default(true);
+    /// assert_eq!(input.rendered_prompt().unwrap(), "> Enter a number [7]: ");
+    /// ```
+    pub fn rendered_prompt(&self) -> Option<String> {
+        let prompt = self.view.prompt.as_ref()?;
+        let remaining = self.budget.as_ref().map(|budget| (budget.remaining)());
+        let default_hint = self
+            .show_default
+            .and_then(|show| self.default.as_ref().map(show));
+        // without a suffix treat trailing punctuation as the suffix, so that
+        // the default is displayed before it
+        let (prompt, suffix) = match (&self.view.suffix, &default_hint) {
+            (None, Some(_)) => {
+                let trimmed = prompt
+                    .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '?' | '>'));
+                (trimmed, Some(&prompt[trimmed.len()..]))
+            }
+            (suffix, _) => (prompt.as_str(), suffix.as_deref()),
+        };
+        let mut p = String::new();
+        if let Some(prefix) = &self.view.prefix {
+            p.push_str(prefix);
+        }
+        if let Some(marker) = &self.view.required {
+            p.push_str(marker);
+        }
+        if let Some(remaining) = remaining {
+            p.push_str(&format!("[remaining: {}] ", remaining));
+        }
+        p.push_str(prompt);
+        if let Some(default) = default_hint {
+            p.push_str(&format!(" [{}]", default));
+        }
+        if let Some(suffix) = suffix {
+            p.push_str(suffix);
+        }
+        match &self.read.terminator {
+            Some(terminator) => p.push_str(&format!(
+                "(end with a line containing \"{}\")\n",
+                terminator
+            )),
+            None if self.read.multiline => p.push_str(match cfg!(windows) {
+                true => "(end with Ctrl-Z)\n",
+                false => "(end with Ctrl-D)\n",
+            }),
+            None => {}
+        }
+        Some(p)
+    }
+
+    /// Returns the default value, computing it if necessary.
+    fn take_default(&mut self) -> Option<T> {
+        let default_with = &self.default_with;
+        self.default
+            .take()
+            .or_else(|| default_with.as_ref().map(|default| default()))
+    }
+
+    /// Reads the rest of a multi-line block that starts with `line`, until
+    /// the terminator or the end of input.
+    ///
+    /// Each line in the block ends with `\n`, whatever line ending was read.
+    /// If a line fails validation the block up to and including that line is
+    /// returned along with the error message.
+    fn read_block(
+        &self,
+        mut line: String,
+        read_line: &mut ReadLineFn<'_>,
+        out: &mut dyn Write,
+    ) -> io::Result<(String, Option<String>)> {
+        let terminator = self.read.terminator.as_deref();
+        let mut block = String::new();
+        let mut ended = line.is_empty();
+        let mut number = 0;
+        while !ended && terminator != Some(line.trim_end_matches(['\n', '\r'])) {
+            let content = line.trim_end_matches(['\n', '\r']);
+            block.push_str(content);
+            block.push('\n');
+            number += 1;
+            for validate in &self.read.line_validators {
+                if let Err(msg) = validate(content) {
+                    return Ok((block, Some(format!("line {}: {}", number, msg))));
+                }
+            }
+            line = read_line(&None, out)?;
+            ended = line.is_empty();
+        }
+        let block = match block.is_empty() && !ended {
+            true => "\n".to_string(),
+            false => block,
+        };
+        Ok((block, None))
+    }
+
+    /// Returns the line with the line ending and any trimmed characters
+    /// removed.
+    fn trimmed<'a>(&self, line: &'a str) -> &'a str {
+        let unterminated = line.trim_end_matches(['\n', '\r']);
+        match (&self.text.trim, self.read.multiline) {
+            (Some(Trim::Whitespace), _) | (None, false) => line.trim(),
+            (Some(Trim::Chars(chars)), _) => unterminated.trim_matches(&chars[..]),
+            (Some(Trim::Off), _) => unterminated,
+            // only remove the final line ending of a block
+            (None, true) => line.strip_suffix('\n').unwrap_or(line),
+        }
+    }
+
+    /// Parses and validates a single non-empty input.
+    ///
+    /// If the parser reports where the error is, it is stored in
+    /// `error_position`.
+    fn attempt(
+        &self,
+        raw: &str,
+        options: Option<&[String]>,
+        error_position: &mut Option<usize>,
+    ) -> Result<T, AttemptOutcome> {
+        let text = &self.text;
+        let parser = &self.parser;
+        if let Some((_, hard)) = text.length_limits {
+            if raw.chars().count() > hard {
+                return Err(AttemptOutcome::Invalid(format!(
+                    "input must be at most {} characters",
+                    hard
+                )));
+            }
+        }
+        let raw = match &text.preview {
+            Some(preview) => Cow::Owned(preview(raw)),
+            None => Cow::Borrowed(raw),
+        };
+        let raw = text
+            .transforms
+            .iter()
+            .fold(raw, |raw, transform| Cow::Owned(transform(&raw)));
+        let raw = match text.number_words.then(|| number_word(&raw)).flatten() {
+            Some(n) => Cow::Owned(n.to_string()),
+            None => raw,
+        };
+        #[cfg(feature = "arithmetic")]
+        let raw = match text.arithmetic && parser.run(&raw).is_err() {
+            true => match arithmetic::evaluate(&raw) {
+                Some(value) => Cow::Owned(value.map_err(AttemptOutcome::ParseError)?.to_string()),
+                None => raw,
+            },
+            false => raw,
+        };
+        let raw = raw.as_ref();
+        if let Some(options) = options {
+            if !options.iter().any(|option| option == raw) {
+                return Err(AttemptOutcome::Invalid(format!(
+                    "expected one of: {}",
+                    options.join(", ")
+                )));
+            }
+        }
+        let value = match parser.run_detailed(raw) {
+            Err((Some(position), err)) => {
+                *error_position = Some(position);
+                Err(err)
+            }
+            Err((None, err)) if text.round_floats => match raw.parse::<f64>() {
+                Ok(float) if float.is_finite() => {
+                    // add zero to avoid displaying negative zero
+                    let rounded = float.round() + 0.0;
+                    parser
+                        .run(&rounded.to_string())
+                        .map_err(|_| format!("{} is out of range", rounded))
+                }
+                _ => Err(err),
+            },
+            result => result.map_err(|(_, err)| err),
+        }
+        .map_err(AttemptOutcome::ParseError)?;
+        if !self.validators.is_empty() {
+            let folded = match &text.fold {
+                Some(fold) => Some(parser.run(&fold(raw)).map_err(AttemptOutcome::ParseError)?),
+                None => None,
+            };
+            let value = folded.as_ref().unwrap_or(&value);
+            for validator in &self.validators {
+                validator.run(value).map_err(AttemptOutcome::Invalid)?;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Records an accepted value and displays any warnings about the input it
+    /// was parsed from.
+    fn accept(&self, value: &T, raw: &str, output: &mut Output) -> io::Result<()> {
+        if let Some(budget) = &self.budget {
+            (budget.spend)(value);
+        }
+        if let Some(save) = &self.save {
+            save(value);
+        }
+        if let Some((old, suggestion)) = self.text.deprecated.iter().find(|(old, _)| old == raw) {
+            writeln!(
+                output.err(),
+                "Warning: `{}` is deprecated, use `{}` instead",
+                old,
+                suggestion
+            )?;
+        }
+        if let Some((soft, _)) = self.text.length_limits {
+            if raw.chars().count() > soft {
+                writeln!(
+                    output.err(),
+                    "Warning: input is longer than {} characters",
+                    soft
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the messages to display after the given error, or calls the
+    /// error handler instead if there is one.
+    fn error_messages(&self, msg: &str, failures: usize) -> Vec<String> {
+        let view = &self.view;
+        let mut messages = Vec::new();
+        match &view.on_error {
+            Some(on_error) => on_error(msg),
+            None => {
+                let label = match &view.error_style {
+                    Some(style) => style("Error:"),
+                    None => "Error:".to_string(),
+                };
+                messages.push(format!("{} {}", label, msg))
+            }
+        }
+        if let Some(help) = view.help.get(failures).or_else(|| view.help.last()) {
+            messages.push(help.clone());
+        }
+        messages
+    }
+
+    fn try_get_with(
+        mut self,
+        read_line: &mut ReadLineFn<'_>,
+        output: &mut Output,
+    ) -> Result<(T, Vec<(String, AttemptOutcome)>), CasualError> {
+        let prompt = self.rendered_prompt();
+        // the unstyled prompt is still used to measure its width
+        let styled = match &self.view.prompt_style {
+            Some(style) => prompt.as_deref().map(style),
+            None => prompt.clone(),
+        };
+        let is_required = self.view.required.is_some();
+        let options = self.text.options.as_ref().map(|options| options());
+
+        if let Some(start) = self.view.start.take() {
+            start();
+        }
+
+        let mut history = Vec::new();
+        let mut failures = 0;
+        // the number of lines drawn since the context and the last messages
+        let mut drawn = 0;
+        let mut messages = Vec::new();
+        let value = loop {
+            let context = &self.view.context;
+            if !context.is_empty() && (output.interactive || history.is_empty()) {
+                let out = output.out();
+                if drawn > 0 {
+                    // clear the previous context and everything after it
+                    write!(out, "\x1b[{}A\r\x1b[J", drawn)?;
+                }
+                for line in context.iter().chain(&messages) {
+                    writeln!(out, "{}", line)?;
+                }
+                drawn = context.len() + messages.len() + 1;
+            }
+            let mut line = match read_line(&styled, output.out()) {
+                Err(err) if err.kind() == io::ErrorKind::TimedOut => {
+                    // the user did not respond, use the default if there is one
+                    writeln!(output.out())?;
+                    match self.take_default() {
+                        Some(default) if !is_required => {
+                            self.accept(&default, "", output)?;
+                            history.push((String::new(), AttemptOutcome::Accepted));
+                            break default;
+                        }
+                        _ => return Err(err.into()),
+                    }
+                }
+                result => result?,
+            };
+            let mut invalid_line = None;
+            if self.read.multiline {
+                let (block, invalid) = self.read_block(line, read_line, output.out())?;
+                line = block;
+                invalid_line = invalid;
+            }
+            if line.is_empty() {
+                // the end of input was reached, use the default if there is one
+                match self.take_default() {
+                    Some(default) if !is_required => {
+                        self.accept(&default, "", output)?;
+                        history.push((line, AttemptOutcome::Accepted));
+                        break default;
+                    }
+                    _ => return Err(CasualError::Eof),
+                }
+            }
+            let unterminated = line.trim_end_matches(['\n', '\r']);
+            let mut raw = Cow::Borrowed(self.trimmed(&line));
+            if self.text.abort.iter().any(|word| word == &*raw) {
+                return Err(CasualError::Cancelled);
+            }
+            if let Some((typo, fix)) = self
+                .text
+                .corrections
+                .iter()
+                .filter(|_| invalid_line.is_none())
+                .find(|(typo, _)| raw.contains(typo.as_str()))
+            {
+                let corrected = raw.replace(typo.as_str(), fix);
+                let (yes, answers) = Confirm::new(format!("Did you mean `{}`?", corrected))
+                    .input()
+                    .try_get_with(read_line, output)?;
+                // each invalid answer is followed by an error message
+                drawn += 2 * answers.len() - 1;
+                if yes {
+                    raw = Cow::Owned(corrected);
+                }
+            }
+            let raw = raw.as_ref();
+            let is_empty = self
+                .text
+                .empty
+                .as_ref()
+                .map_or_else(|| raw.is_empty(), |empty| empty(raw));
+            let is_whitespace = !unterminated.is_empty() && raw.is_empty();
+            let mut error_position = None;
+            let result = if let Some(msg) = invalid_line {
+                Err(AttemptOutcome::Invalid(msg))
+            } else if self.text.whitespace_is_invalid && is_whitespace {
+                Err(AttemptOutcome::Invalid(
+                    "input cannot be only whitespace".to_string(),
+                ))
+            } else if is_empty {
+                if is_required {
+                    Err(AttemptOutcome::Invalid("a value is required".to_string()))
+                } else if let Some(default) = self.take_default() {
+                    Ok(default)
+                } else if self.text.empty_is_error {
+                    return Err(CasualError::Empty);
+                } else {
+                    Err(AttemptOutcome::Empty)
+                }
+            } else {
+                let result = self.attempt(raw, options.as_deref(), &mut error_position);
+                if let (Ok(value), Some(debug)) = (&result, self.debug) {
+                    writeln!(output.err(), "Parsed: {}", debug(value))?;
+                }
+                result
+            };
+            let line = unterminated.to_string();
+            match result {
+                Ok(value) => {
+                    self.accept(&value, raw, output)?;
+                    history.push((line, AttemptOutcome::Accepted));
+                    break value;
+                }
+                Err(outcome) => {
+                    if let AttemptOutcome::ParseError(msg) | AttemptOutcome::Invalid(msg) = &outcome
+                    {
+                        if let Some(position) = error_position {
+                            // point at the error in the line the user entered
+                            let width = prompt
+                                .as_deref()
+                                .and_then(|prompt| prompt.lines().last())
+                                .map_or(0, |prompt| prompt.chars().count());
+                            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                            writeln!(output.out(), "{}^", " ".repeat(width + indent + position))?;
+                            drawn += 1;
+                        }
+                        messages = self.error_messages(msg, failures);
+                        for message in &messages {
+                            writeln!(output.out(), "{}", message)?;
+                        }
+                        drawn += messages.len();
+                        failures += 1;
+                    }
+                    history.push((line, outcome));
+                    if self
+                        .read
+                        .max_attempts
+                        .is_some_and(|max| history.len() >= max)
+                    {
+                        return Err(CasualError::TooManyAttempts);
+                    }
+                }
+            }
+        };
+        Ok((value, history))
+    }
+
+    fn try_get_with_output(
+        mut self,
+        mut output: Output,
+    ) -> Result<(T, Vec<(String, AttemptOutcome)>), CasualError> {
+        let limit = self.read.limit;
+        let remind = self.read.remind.filter(|_| output.interactive);
+        let timeout = self.read.timeout;
+        let countdown = self.read.countdown && output.interactive;
+        let secret = self.read.secret;
+        let mask = self.read.mask;
+        let interactive = output.interactive;
+        let preview = match (&self.text.preview, &self.text.display) {
+            (Some(preview), _) => Some((preview.clone(), false)),
+            (None, Some(display)) => Some((display.clone(), true)),
+            (None, None) => None,
+        };
+        let fallback = self.fallback.take();
+        let mut read_line = move |prompt: &Option<String>, out: &mut dyn Write| {
+            if secret {
+                return read_line_secret(prompt, out, limit, mask);
+            }
+            match (&preview, remind) {
+                (Some((preview, beside)), _) => {
+                    read_line_preview(prompt, out, limit, &**preview, *beside, interactive)
+                }
+                (None, None) if timeout.is_none() => read_line(prompt, out, limit),
+                (None, interval) => read_line_waiting(
+                    &STDIN_PENDING,
+                    move || read_from(&mut io::stdin().lock(), limit),
+                    prompt,
+                    out,
+                    interval,
+                    timeout,
+                    countdown,
+                ),
+            }
+        };
+        let result = self.try_get_with(&mut read_line, &mut output);
+        match (result, fallback) {
+            (Err(err), Some(fallback)) if err.is_abandoned() => {
+                fallback.try_get_with_output(output)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn try_get_from_output<R: BufRead>(
+        mut self,
+        reader: &mut R,
+        mut output: Output,
+    ) -> Result<T, CasualError> {
+        let limit = self.read.limit;
+        let fallback = self.fallback.take();
+        let mut read_line = |prompt: &Option<String>, out: &mut dyn Write| {
+            read_line_from(reader, prompt, out, limit)
+        };
+        let result = self.try_get_with(&mut read_line, &mut output);
+        match (result, fallback) {
+            (Err(err), Some(fallback)) if err.is_abandoned() => {
+                fallback.try_get_from_output(reader, output)
+            }
+            (result, _) => result.map(|(value, _)| value),
+        }
+    }
+
+    #[inline]
+    fn try_get_with_history(self) -> Result<(T, Vec<(String, AttemptOutcome)>), CasualError> {
+        let (mut stdout, mut stderr) = (io::stdout(), io::stderr());
+        let output = Output::std(self.view.stderr, &mut stdout, &mut stderr);
+        self.try_get_with_output(output)
+    }
+
+    /// Consumes the `Input` and reads the input from the user, returning an
+    /// error instead of panicking.
+    ///
+    /// This is the same as [`.get()`](#method.get) except that errors are
+    /// returned. If the end of input is reached, for example because the user
+    /// pressed Ctrl-D, and there is no default then
+    /// [`CasualError::Eof`](enum.CasualError.html#variant.Eof) is returned.
+    ///
+    /// ```no_run
+    /// # use casual::{CasualError, Input};
+    /// match Input::<u32>::new().prompt("Enter a number: ").try_get() {
+    ///     Ok(num) => println!("got {}", num),
+    ///     Err(CasualError::Eof) => println!("bye!"),
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_get(self) -> Result<T, CasualError> {
+        self.try_get_with_history().map(|(value, _)| value)
+    }
+
+    /// Consumes the `Input` and reads the input from the user.
+    ///
+    /// This function uses [`FromStr`] to parse the input data.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let num: u32 = Input::new().prompt("Enter a number: ").get();
+    /// ```
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    pub fn get(self) -> T {
+        self.try_get().unwrap()
+    }
+
+    /// Consumes the `Input` and reads the input from the user, returning
+    /// `None` at the end of input.
+    ///
+    /// This is the same as [`.get()`](#method.get) except that if the end of
+    /// input is reached and there is no default, or the user
+    /// [cancels](enum.CasualError.html#variant.Cancelled) the input, `None` is
+    /// returned instead of panicking. Other errors still panic. This is useful
+    /// for reading until the user is done.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let mut total = 0;
+    /// while let Some(num) = Input::<u32>::new().prompt("Enter a number: ").get_opt() {
+    ///     total += num;
+    /// }
+    /// println!("Total: {}", total);
+    /// ```
+    pub fn get_opt(self) -> Option<T> {
+        match self.try_get() {
+            Ok(value) => Some(value),
+            Err(CasualError::Eof | CasualError::Cancelled) => None,
+            Err(err) => panic!("failed to read input: {}", err),
+        }
+    }
+
+    /// Consumes the `Input` and reads the input from the user, returning
+    /// `default` if the user enters an empty input.
+    ///
+    /// This is a shortcut for `.default(default).get()`.
+    ///
+    /// ```no_run
+    /// # use casual::prompt;
+    /// let num = prompt("Enter a number [5]: ").get_with_default(5);
+    /// ```
+    pub fn get_with_default(self, default: T) -> T {
+        self.default(default).get()
+    }
+
+    /// Consumes the `Input` and reads the input from the user, writing all
+    /// output to the given writer.
+    ///
+    /// Input is still read from stdin but the prompt, any error messages, and
+    /// any warnings are written to `writer` instead of stdout and stderr.
+    /// This is useful to capture exactly what the user was shown.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a number: ")
+    ///     .get_with_writer(&mut transcript)?;
+    /// assert!(transcript.starts_with(b"Enter a number: "));
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn get_with_writer<W: Write>(self, mut writer: W) -> Result<T, CasualError> {
+        self.try_get_with_output(Output {
+            out: &mut writer,
+            err: None,
+            interactive: false,
+        })
+        .map(|(value, _)| value)
+    }
+
+    /// Consumes the `Input` and reads the input from the given reader.
+    ///
+    /// This is the same as [`.try_get()`](#method.try_get) except that input
+    /// is read from `reader` instead of stdin. The prompt and any messages
+    /// are still written to stdout and stderr. Options that need a terminal,
+    /// like [`.live_preview()`](#method.live_preview), have no effect.
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a number: ")
+    ///     .get_from(&b"42\n"[..])?;
+    /// assert_eq!(num, 42);
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn get_from<R: BufRead>(self, mut reader: R) -> Result<T, CasualError> {
+        let (mut stdout, mut stderr) = (io::stdout(), io::stderr());
+        let output = Output {
+            interactive: false,
+            ..Output::std(self.view.stderr, &mut stdout, &mut stderr)
+        };
+        self.try_get_from_output(&mut reader, output)
+    }
+
+    /// Consumes the `Input` and reads the input from the given reader,
+    /// writing all output to the given writer.
+    ///
+    /// This combines [`.get_from()`](#method.get_from) and
+    /// [`.get_with_writer()`](#method.get_with_writer), which makes it
+    /// possible to test prompts without a terminal.
+    ///
+    /// ```
+    /// # use casual::Input;
+    /// let mut transcript = Vec::new();
+    /// let num: u32 = Input::new()
+    ///     .prompt("Enter a number: ")
+    ///     .get_from_rw(&b"ten\n10\n"[..], &mut transcript)?;
+    /// assert_eq!(num, 10);
+    /// assert_eq!(
+    ///     String::from_utf8(transcript).unwrap(),
+    ///     "Enter a number: Error: invalid digit found in string\nEnter a number: "
+    /// );
+    /// # Ok::<(), casual::CasualError>(())
+    /// ```
+    pub fn get_from_rw<R, W>(self, mut reader: R, mut writer: W) -> Result<T, CasualError>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        self.try_get_from_output(
+            &mut reader,
+            Output {
+                out: &mut writer,
+                err: None,
+                interactive: false,
+            },
+        )
+    }
+
+    /// Consumes the `Input` and reads the input from the user, recording
+    /// every attempt.
+    ///
+    /// This is the same as [`.get()`](#method.get) except that it also
+    /// returns each line the user entered, in order, along with what
+    /// happened to it. This is useful when debugging input handling.
+    ///
+    /// ```no_run
+    /// # use casual::{AttemptOutcome, Input};
+    /// let (num, history): (u32, _) = Input::new()
+    ///     .prompt("Enter a number: ")
+    ///     .get_with_history();
+    /// for (line, outcome) in history {
+    ///     if outcome != AttemptOutcome::Accepted {
+    ///         eprintln!("rejected {:?}: {:?}", line, outcome);
+    ///     }
+    /// }
+    /// ```
+    pub fn get_with_history(self) -> (T, Vec<(String, AttemptOutcome)>) {
+        self.try_get_with_history().unwrap()
+    }
+
+    /// Consumes the `Input` and applies the given function to it.
+    ///
+    /// This function uses [`FromStr`] to parse the input data. The result is
+    /// then fed to the given closure.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let value = Input::new().map(|s: String| &s.to_lowercase() == "yes");
+    /// ```
+    ///
+    /// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+    pub fn map<F, U>(self, map: F) -> U
+    where
+        F: Fn(T) -> U,
+    {
+        map(self.get())
+    }
+
+    /// Consumes the `Input` and reads the input from the user, returning the
+    /// value along with whether it passes the given check.
+    ///
+    /// Unlike [`.matches()`](#method.matches), the user is not asked again if
+    /// the check fails. The value is returned either way so it does not need
+    /// to be cloned.
+    ///
+    /// ```no_run
+    /// # use casual::Input;
+    /// let (port, privileged) = Input::<u16>::new()
+    ///     .prompt("Port: ")
+    ///     .get_checked(|port| *port < 1024);
+    /// ```
+    pub fn get_checked<F>(self, check: F) -> (T, bool)
+    where
+        F: Fn(&T) -> bool,
+    {
+        let value = self.get();
+        let passed = check(&value);
+        (value, passed)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////
+// Shortcut functions
+/////////////////////////////////////////////////////////////////////////
 
 /// Returns a new empty `Input`.
 ///
@@ -281,7 +3822,11 @@ where
 /// # use casual::input;
 /// let data: String = input().get();
 /// ```
-pub fn input<T>() -> Input<T> {
+pub fn input<T>() -> Input<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
     Input::new()
 }
 
@@ -309,10 +3854,255 @@ pub fn input<T>() -> Input<T> {
 pub fn prompt<S, T>(text: S) -> Input<T>
 where
     S: Into<String>,
+    T: FromStr,
+    <T as FromStr>::Err: Display,
 {
     Input::new().prompt(text)
 }
 
+/// Prompts the user for several values of the same type, one after another.
+///
+/// Each label is used as the prompt for one value and the values are
+/// returned in the same order.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::prompt_fields;
+/// let dimensions: Vec<u32> = prompt_fields(&["Width: ", "Height: ", "Depth: "]);
+/// ```
+pub fn prompt_fields<T>(labels: &[&str]) -> Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    labels.iter().map(|label| prompt(*label).get()).collect()
+}
+
+/// Returns an `Input` that prompts the user for a single character.
+///
+/// Unlike reading a `char` using [`FromStr`] this reports a friendly error
+/// if the user does not enter exactly one character. Use
+/// [`.allowed()`](struct.Input.html#method.allowed) to restrict which
+/// characters are accepted.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::prompt_char;
+/// let key = prompt_char("Press a key and then enter: ").get();
+/// ```
+///
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+pub fn prompt_char<S: Into<String>>(text: S) -> Input<char> {
+    Input::from_parser(Parser::new(parse_char)).prompt(text)
+}
+
+/// Prompts the user to choose one of the given options.
+///
+/// The options are displayed as a numbered menu above the prompt, using
+/// their `Display` implementation as the label. The user can enter either the
+/// number or the label of an option. A [`.default()`] can be set to one of
+/// the options to use it when the user enters an empty input.
+///
+/// # Examples
+///
+/// ```
+/// let mut transcript = Vec::new();
+/// let color = casual::select("Color: ", vec!["red", "green", "blue"])
+///     .get_from_rw(&b"4\ngreen\n"[..], &mut transcript)?;
+/// assert_eq!(color, "green");
+/// assert_eq!(
+///     String::from_utf8(transcript).unwrap(),
+///     "  1) red\n  2) green\n  3) blue\nColor: \
+///      Error: please enter a number between 1 and 3\nColor: "
+/// );
+/// # Ok::<(), casual::CasualError>(())
+/// ```
+///
+/// [`.default()`]: struct.Input.html#method.default
+pub fn select<S, T>(text: S, options: Vec<T>) -> Input<T>
+where
+    S: Into<String>,
+    T: Clone + Display + 'static,
+{
+    let labels: Vec<String> = options.iter().map(ToString::to_string).collect();
+    let menu = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| format!("  {}) {}", i + 1, label))
+        .collect();
+    let parser = Parser::new(move |s| {
+        if let Some(i) = labels.iter().position(|label| label == s) {
+            return Ok(options[i].clone());
+        }
+        match s.parse::<usize>() {
+            Ok(n) if (1..=options.len()).contains(&n) => Ok(options[n - 1].clone()),
+            Ok(_) => Err(format!(
+                "please enter a number between 1 and {}",
+                options.len()
+            )),
+            Err(_) => Err("please enter the number or name of an option".to_string()),
+        }
+    });
+    Input::from_parser(parser).prompt(text).context_lines(menu)
+}
+
+/// Prompts the user for a range.
+///
+/// The range is entered as `start..end`, or `start..=end` to include the
+/// end. Each bound is parsed using [`FromStr`] and the start must not be
+/// greater than the end.
+///
+/// # Examples
+///
+/// ```
+/// # use casual::{prompt_range, InputRange};
+/// let mut transcript = Vec::new();
+/// let lines = prompt_range::<usize, _>("Lines to print: ")
+///     .matches(|range| !range.is_empty())
+///     .get_from_rw(&b"3..3\n1..=10\n"[..], &mut transcript)?;
+/// assert_eq!(lines, InputRange::Inclusive(1..=10));
+/// # Ok::<(), casual::CasualError>(())
+/// ```
+///
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+pub fn prompt_range<T, S>(text: S) -> Input<InputRange<T>>
+where
+    T: FromStr + PartialOrd + 'static,
+    <T as FromStr>::Err: Display,
+    S: Into<String>,
+{
+    Input::from_parser(Parser::new(parse_range::<T>)).prompt(text)
+}
+
+fn parse_range<T>(s: &str) -> Result<InputRange<T>, String>
+where
+    T: FromStr + PartialOrd,
+    <T as FromStr>::Err: Display,
+{
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| "expected a range like `start..end`".to_string())?;
+    let parse = |bound: &str| {
+        bound
+            .trim()
+            .parse::<T>()
+            .map_err(|err| format!("invalid range bound `{}`: {}", bound.trim(), err))
+    };
+    let start = parse(start)?;
+    let (inclusive, end) = match end.strip_prefix('=') {
+        Some(end) => (true, parse(end)?),
+        None => (false, parse(end)?),
+    };
+    if start > end {
+        return Err("range start must not be greater than the end".to_string());
+    }
+    Ok(match inclusive {
+        true => InputRange::Inclusive(start..=end),
+        false => InputRange::Exclusive(start..end),
+    })
+}
+
+/// Prompts the user for a value and converts it to a slug.
+///
+/// The input is lowercased, runs of whitespace, hyphens, and underscores are
+/// replaced with a single hyphen, and any other non-alphanumeric characters
+/// are removed. If nothing is left the user is prompted again.
+///
+/// # Examples
+///
+/// Typing `My New Project!` results in `my-new-project`.
+///
+/// ```no_run
+/// # use casual::prompt_slug;
+/// let slug = prompt_slug("Project name: ").get();
+/// ```
+pub fn prompt_slug<S: Into<String>>(text: S) -> Input<String> {
+    let mut input = Input::new().prompt(text);
+    input.text.transforms.push(Box::new(slugify));
+    input.validators.push(Validator::with_message(
+        |s: &String| !s.is_empty(),
+        "must contain at least one letter or digit",
+    ));
+    input
+}
+
+/// Converts the given text to a lowercase, hyphen separated slug.
+fn slugify(s: &str) -> String {
+    let mut slug = String::new();
+    for c in s.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_')
+            && !slug.is_empty()
+            && !slug.ends_with('-')
+        {
+            slug.push('-');
+        }
+    }
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Prompts the user for a list of numbers and calculates some statistics.
+///
+/// The numbers can be separated by whitespace or commas. If any of them fail
+/// to parse the user is prompted again.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::prompt_numbers;
+/// let (numbers, stats) = prompt_numbers("Enter some measurements: ");
+/// println!("Read {} numbers with mean {}", numbers.len(), stats.mean);
+/// ```
+pub fn prompt_numbers<S: Into<String>>(text: S) -> (Vec<f64>, Stats) {
+    let numbers = Input::from_parser(Parser::new(parse_numbers))
+        .prompt(text)
+        .get();
+    let stats = Stats::new(&numbers);
+    (numbers, stats)
+}
+
+/// Returns an iterator over values parsed from each line of stdin.
+///
+/// Lines are read lazily until the end of input. Each line is trimmed and
+/// parsed using [`FromStr`], no prompt is displayed and invalid lines are
+/// yielded as errors instead of asking again. Blank lines are yielded as
+/// [`CasualError::Empty`] unless [`.skip_blank()`] is used, and values that
+/// fail a [check](struct.Lines.html#method.matches_with) are yielded as
+/// [`CasualError::Validation`]. This is intended for processing piped input.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut total = 0;
+/// for n in casual::lines::<u32>().skip_blank() {
+///     total += n?;
+/// }
+/// println!("{}", total);
+/// # Ok::<(), casual::CasualError>(())
+/// ```
+///
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
+/// [`CasualError::Empty`]: enum.CasualError.html#variant.Empty
+/// [`CasualError::Validation`]: enum.CasualError.html#variant.Validation
+/// [`.skip_blank()`]: struct.Lines.html#method.skip_blank
+pub fn lines<T>() -> Lines<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    Lines {
+        skip_blank: false,
+        done: false,
+        validators: Vec::new(),
+    }
+}
+
 /// Prompts the user for confirmation (yes/no).
 ///
 /// # Examples
@@ -326,9 +4116,697 @@ where
 /// }
 /// ```
 pub fn confirm<S: Into<String>>(text: S) -> bool {
-    prompt(text)
-        .suffix(" [y/N] ")
-        .default("n".to_string())
-        .matches(|s| matches!(&*s.trim().to_lowercase(), "n" | "no" | "y" | "yes"))
-        .map(|s| matches!(&*s.to_lowercase(), "y" | "yes"))
+    Confirm::new(text).get()
+}
+
+/// Prompts for confirmation (yes/no), taking the answers from an iterator.
+///
+/// This works exactly like [`confirm`] except that the answer is the next
+/// item in `answers` instead of a line read from stdin, and nothing is
+/// written to stdout. If there are no answers left the default (no) is used.
+/// This is useful for testing and scripting.
+///
+/// # Examples
+///
+/// ```
+/// # use casual::confirm_from_iter;
+/// let mut answers = vec!["yes", "maybe", "n"].into_iter().map(String::from);
+/// assert!(confirm_from_iter("Continue?", &mut answers));
+/// assert!(!confirm_from_iter("Continue?", &mut answers));
+/// assert!(!confirm_from_iter("Continue?", &mut answers));
+/// ```
+///
+/// [`confirm`]: fn.confirm.html
+pub fn confirm_from_iter<S, I>(text: S, answers: &mut I) -> bool
+where
+    S: Into<String>,
+    I: Iterator<Item = String>,
+{
+    let mut read_line = |_: &Option<String>, _: &mut dyn Write| {
+        Ok(answers
+            .next()
+            .map(|answer| answer + "\n")
+            .unwrap_or_default())
+    };
+    let output = &mut Output {
+        out: &mut io::sink(),
+        err: None,
+        interactive: false,
+    };
+    let (yes, _) = Confirm::new(text)
+        .input()
+        .try_get_with(&mut read_line, output)
+        .unwrap();
+    yes
+}
+
+/// Prompts the user to toggle a setting on or off.
+///
+/// The current state is displayed and pressing enter keeps it, otherwise a
+/// yes/no answer sets it explicitly.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::prompt_toggle;
+/// let dark_mode = prompt_toggle("Dark mode", true);
+/// ```
+pub fn prompt_toggle<S: Into<String>>(text: S, current: bool) -> bool {
+    let suffix = match current {
+        true => " (currently on) [y/n] ",
+        false => " (currently off) [y/n] ",
+    };
+    Confirm::new(text)
+        .default(current)
+        .input()
+        .suffix(suffix)
+        .get()
+}
+
+/// Prompts the user with a checkbox that is toggled using the spacebar.
+///
+/// The checkbox starts in the `default` state and pressing enter confirms
+/// it. If stdin and stdout are not a terminal, or the terminal can not be
+/// put into raw mode, this falls back to a yes/no question like
+/// [`confirm`](fn.confirm.html) instead. Raw mode is currently only supported
+/// on Unix.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use casual::prompt_checkbox;
+/// let subscribe = prompt_checkbox("Subscribe to the newsletter", false);
+/// ```
+pub fn prompt_checkbox<S: Into<String>>(text: S, default: bool) -> bool {
+    let text = text.into();
+    match read_checkbox(&text, default).unwrap() {
+        Some(checked) => checked,
+        None => Confirm::new(text).default(default).get(),
+    }
+}
+
+/// Returns whether a value passes the Luhn checksum.
+///
+/// The value is formatted using `Display` and must consist only of digits,
+/// spaces and hyphens are ignored. This is intended to be used with
+/// [`Input::checksum()`](struct.Input.html#method.checksum).
+///
+/// # Examples
+///
+/// ```
+/// # use casual::luhn;
+/// assert!(luhn(&79927398713u64));
+/// assert!(luhn(&"4111 1111 1111 1111"));
+/// assert!(!luhn(&"4111 1111 1111 1112"));
+/// ```
+pub fn luhn<T: Display + ?Sized>(value: &T) -> bool {
+    let value = value.to_string();
+    let mut digits = value.chars().filter(|c| *c != ' ' && *c != '-').peekable();
+    if digits.peek().is_none() {
+        return false;
+    }
+    let mut sum = 0;
+    for (i, c) in digits.rev().enumerate() {
+        let digit = match c.to_digit(10) {
+            Some(digit) => digit,
+            None => return false,
+        };
+        sum += match i % 2 {
+            0 => digit,
+            _ if digit > 4 => digit * 2 - 9,
+            _ => digit * 2,
+        };
+    }
+    sum % 10 == 0
+}
+
+/////////////////////////////////////////////////////////////////////////
+// Macros
+/////////////////////////////////////////////////////////////////////////
+
+/// Returns a new `Input` with the given prompt, using format arguments.
+///
+/// This is shorthand for [`prompt(format!(...))`](fn.prompt.html).
+///
+/// # Examples
+///
+/// ```
+/// let name = "width";
+/// let mut transcript = Vec::new();
+/// let width: u32 = casual::prompt!("Enter {} value: ", name)
+///     .get_from_rw(&b"80\n"[..], &mut transcript)?;
+/// assert_eq!(width, 80);
+/// assert_eq!(String::from_utf8(transcript).unwrap(), "Enter width value: ");
+/// # Ok::<(), casual::CasualError>(())
+/// ```
+#[macro_export]
+macro_rules! prompt {
+    ($($arg:tt)*) => {
+        $crate::prompt(format!($($arg)*))
+    };
+}
+
+/// Prompts the user for confirmation (yes/no), using format arguments.
+///
+/// This is shorthand for [`confirm(format!(...))`](fn.confirm.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// let path = "config.toml";
+/// if casual::confirm!("Overwrite {}?", path) {
+///     // overwrite
+/// }
+/// ```
+#[macro_export]
+macro_rules! confirm {
+    ($($arg:tt)*) => {
+        $crate::confirm(format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    /// A reader that is interrupted once before each read.
+    struct Interrupted<'a> {
+        interrupt: bool,
+        bytes: &'a [u8],
+    }
+
+    impl Read for Interrupted<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if !self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            self.bytes.read(buf)
+        }
+    }
+
+    /// Reads `input` from `lines`, writing everything that is displayed to
+    /// one transcript like `get_with_writer()` does.
+    fn transcript<T>(input: Input<T>, mut lines: &[u8]) -> (Result<T, CasualError>, String) {
+        let mut transcript = Vec::new();
+        let mut output = Output {
+            out: &mut transcript,
+            err: None,
+            interactive: false,
+        };
+        let mut read_line = |prompt: &Option<String>, out: &mut dyn Write| {
+            read_line_from(&mut lines, prompt, out, None)
+        };
+        let result = input
+            .try_get_with(&mut read_line, &mut output)
+            .map(|(value, _)| value);
+        (result, String::from_utf8(transcript).unwrap())
+    }
+
+    #[test]
+    fn get_with_writer_transcript() {
+        let input = Input::<u32>::new()
+            .prompt("Count: ")
+            .accept_number_words()
+            .deprecate("ten", "10")
+            .range(1..=10)
+            .length_limits(2, 5)
+            .help_levels(vec!["Enter a whole number".to_string()])
+            .echo_debug();
+        let (result, transcript) = transcript(input, b"abc\n11\nten\n");
+        assert_eq!(result.unwrap(), 10);
+        assert_eq!(
+            transcript,
+            "Count: Error: invalid digit found in string\n\
+             Enter a whole number\n\
+             Count: Error: must be between 1 and 10\n\
+             Enter a whole number\n\
+             Count: Parsed: 10\n\
+             Warning: `ten` is deprecated, use `10` instead\n\
+             Warning: input is longer than 2 characters\n"
+        );
+    }
+
+    #[test]
+    fn get_with_writer_transcript_eof() {
+        let input = Input::<u32>::new().prompt("Count: ");
+        let (result, transcript) = transcript(input, b"-1\n");
+        assert!(matches!(result, Err(CasualError::Eof)));
+        assert_eq!(
+            transcript,
+            "Count: Error: invalid digit found in string\nCount: "
+        );
+    }
+
+    #[test]
+    fn suggest_corrections_asks_until_answered() {
+        let input = Input::<String>::new()
+            .prompt("Host: ")
+            .suggest_corrections(&[("locahost", "localhost")]);
+        let (result, transcript) = transcript(input, b"locahost\nmaybe\nno\n");
+        assert_eq!(result.unwrap(), "locahost");
+        assert_eq!(
+            transcript,
+            "Host: Did you mean `localhost`? [y/N] Error: please answer yes or no\n\
+             Did you mean `localhost`? [y/N] "
+        );
+    }
+
+    #[test]
+    fn confirm_from_iter_uses_confirm_words() {
+        let mut answers = ["Y", "No", "", "yes"].iter().map(|s| s.to_string());
+        assert!(confirm_from_iter("Continue?", &mut answers));
+        assert!(!confirm_from_iter("Continue?", &mut answers));
+        // an empty answer uses the default
+        assert!(!confirm_from_iter("Continue?", &mut answers));
+        assert!(confirm_from_iter("Continue?", &mut answers));
+        assert!(!confirm_from_iter("Continue?", &mut answers));
+    }
+
+    #[test]
+    fn or_else_only_when_abandoned() {
+        let fallback = || Input::<u32>::new().prompt("Fallback: ");
+        let read = |input: Input<u32>, lines: &[u8]| {
+            let mut transcript = Vec::new();
+            input.get_from_rw(lines, &mut transcript)
+        };
+        let input = Input::new().abort_on(&["skip"]).or_else(fallback());
+        assert_eq!(read(input, b"skip\n7\n").unwrap(), 7);
+        let input = Input::new().read_limit(2).or_else(fallback());
+        assert!(matches!(
+            read(input, b"1000\n7\n"),
+            Err(CasualError::Io(err)) if err.get_ref().is_some_and(|err| err.is::<LineTooLong>())
+        ));
+        let input = Input::new().max_attempts(1).or_else(fallback());
+        assert!(matches!(
+            read(input, b"x\n7\n"),
+            Err(CasualError::TooManyAttempts)
+        ));
+        let input = Input::new().or_else(fallback());
+        assert!(matches!(read(input, b""), Err(CasualError::Eof)));
+    }
+
+    /// Reads every line from `input` with the given limit, stopping at the
+    /// end of input.
+    fn read_all(mut input: &[u8], bytes: usize, truncate: bool) -> Vec<Result<String, String>> {
+        let limit = Some(ReadLimit { bytes, truncate });
+        let mut lines = Vec::new();
+        loop {
+            match read_from(&mut input, limit) {
+                Ok(line) if line.is_empty() => break lines,
+                result => lines.push(result.map_err(|err| err.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn read_limit_line_at_limit() {
+        let expected = vec![Ok("abc\n".to_string()), Ok("abc".to_string())];
+        assert_eq!(read_all(b"abc\nabc", 3, false), expected);
+        assert_eq!(read_all(b"abc\nabc", 3, true), expected);
+    }
+
+    #[test]
+    fn read_limit_one_byte_over() {
+        let err = Err("input line exceeds 3 bytes".to_string());
+        assert_eq!(
+            read_all(b"abcd\nab\n", 3, false),
+            vec![err.clone(), Ok("ab\n".to_string())]
+        );
+        assert_eq!(
+            read_all(b"abcd\nab\n", 3, true),
+            vec![Ok("abc".to_string()), Ok("ab\n".to_string())]
+        );
+        assert_eq!(read_all(b"abcd", 3, false), vec![err]);
+    }
+
+    #[test]
+    fn read_limit_truncate_multi_byte_char() {
+        // "é" is two bytes, the limit falls between them
+        assert_eq!(
+            read_all("aé\nb\n".as_bytes(), 2, true),
+            vec![Ok("a".to_string()), Ok("b\n".to_string())]
+        );
+        assert_eq!(
+            read_all("éé\n".as_bytes(), 3, true),
+            vec![Ok("é".to_string())]
+        );
+    }
+
+    #[test]
+    fn read_limit_over_limit_then_eof() {
+        assert_eq!(
+            read_all(b"abcdef", 3, false),
+            vec![Err("input line exceeds 3 bytes".to_string())]
+        );
+        assert_eq!(read_all(b"abcdef", 3, true), vec![Ok("abc".to_string())]);
+    }
+
+    #[test]
+    fn multiline_normalises_line_endings() {
+        let input = Input::<String>::new().multiline_until(".");
+        let (result, _) = transcript(input, b"  indented\r\nnext\n\r\n.\r\n");
+        assert_eq!(result.unwrap(), "  indented\nnext\n");
+        let input = Input::<String>::new().multiline(None);
+        let (result, _) = transcript(input, b"one\r\ntwo");
+        assert_eq!(result.unwrap(), "one\ntwo");
+    }
+
+    #[test]
+    fn multiline_trim_when_set() {
+        let input = Input::<String>::new().multiline(None).trim(true);
+        let (result, _) = transcript(input, b"  indented\r\nnext\n\n");
+        assert_eq!(result.unwrap(), "indented\nnext");
+        let input = Input::<String>::new().multiline(None).trim(false);
+        let (result, _) = transcript(input, b"  indented\r\nnext\n\n");
+        assert_eq!(result.unwrap(), "  indented\nnext");
+    }
+
+    #[test]
+    fn multiline_empty_block_uses_default() {
+        let input = Input::<String>::new()
+            .multiline_until(".")
+            .default("none".to_string());
+        assert_eq!(transcript(input, b".\n").0.unwrap(), "none");
+    }
+
+    #[test]
+    fn validate_lines_stops_at_invalid_line() {
+        let input = Input::<String>::new()
+            .prompt("> ")
+            .multiline(None)
+            .validate_lines(|line| match line.is_empty() {
+                true => Err("blank line".to_string()),
+                false => Ok(()),
+            });
+        let (result, transcript) = transcript(input, b"one\r\n\nthree\nfour\n");
+        assert_eq!(result.unwrap(), "three\nfour");
+        assert_eq!(
+            transcript,
+            "> (end with Ctrl-D)\nError: line 2: blank line\n> (end with Ctrl-D)\n"
+        );
+    }
+
+    #[test]
+    fn read_from_retries_interrupted() {
+        for limit in [
+            None,
+            Some(ReadLimit {
+                bytes: 2,
+                truncate: true,
+            }),
+        ] {
+            let mut reader = io::BufReader::with_capacity(
+                1,
+                Interrupted {
+                    interrupt: true,
+                    bytes: b"ab\ncd\n",
+                },
+            );
+            assert_eq!(read_from(&mut reader, limit).unwrap(), "ab\n");
+            assert_eq!(read_from(&mut reader, limit).unwrap(), "cd\n");
+        }
+    }
+
+    #[test]
+    fn read_line_waiting_keeps_line_after_timeout() {
+        let (reader, mut writer) = io::pipe().unwrap();
+        let reader = Arc::new(Mutex::new(io::BufReader::new(reader)));
+        let read = || {
+            let reader = reader.clone();
+            move || read_from(&mut *reader.lock().unwrap(), None)
+        };
+        let pending = Pending::default();
+        let mut out = Vec::new();
+        let prompt = Some("> ".to_string());
+        let timeout = Some(Duration::from_millis(50));
+
+        let err = read_line_waiting(&pending, read(), &prompt, &mut out, None, timeout, false)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+        // the line entered after the timeout is returned by the next read
+        writer.write_all(b"late\nnext\n").unwrap();
+        let timeout = Some(Duration::from_secs(10));
+        for expected in ["late\n", "next\n"] {
+            let line = read_line_waiting(&pending, read(), &prompt, &mut out, None, timeout, false);
+            assert_eq!(line.unwrap(), expected);
+        }
+        assert_eq!(out, b"> > > ");
+        assert!(take_pending(&pending).is_none());
+    }
+
+    #[test]
+    fn read_line_waiting_counts_down() {
+        let (reader, mut writer) = io::pipe().unwrap();
+        let mut reader = io::BufReader::new(reader);
+        let pending = Pending::default();
+        let mut out = Vec::new();
+        let prompt = Some("> ".to_string());
+        let timeout = Some(Duration::from_millis(1500));
+        let read = move || read_from(&mut reader, None);
+        let result = read_line_waiting(&pending, read, &prompt, &mut out, None, timeout, true);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Proceeding in 2s... > \r\x1b[KProceeding in 1s... > "
+        );
+        writer.write_all(b"late\n").unwrap();
+        assert_eq!(take_pending(&pending).unwrap().unwrap(), "late\n");
+    }
+
+    #[test]
+    fn reader_min_interval() {
+        let interval = Duration::from_millis(100);
+        let mut reader = Reader::from_reader(&b"x\n1\n2\n"[..]).min_interval(interval);
+        let start = Instant::now();
+        // a failed read does not count
+        assert!(reader.read(Input::<u32>::new().max_attempts(1)).is_err());
+        assert_eq!(reader.read(Input::<u32>::new()).unwrap(), 1);
+        assert!(start.elapsed() < interval);
+        let first = Instant::now();
+        assert_eq!(reader.read(Input::<u32>::new()).unwrap(), 2);
+        assert!(first.elapsed() >= interval);
+    }
+
+    #[test]
+    fn budget_spends_default_at_eof() {
+        let points = Rc::new(Cell::new(10));
+        let input = Input::<u32>::new().budget(points.clone()).default(3);
+        let (result, _) = transcript(input, b"");
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(points.get(), 7);
+    }
+
+    #[test]
+    fn take_pending_waits_for_line() {
+        let (reader, mut writer) = io::pipe().unwrap();
+        let mut reader = io::BufReader::new(reader);
+        let pending = Pending::default();
+        let timeout = Some(Duration::from_millis(50));
+        let read = move || read_from(&mut reader, None);
+        let result =
+            read_line_waiting(&pending, read, &None, &mut io::sink(), None, timeout, false);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        writer.write_all(b"late\n").unwrap();
+        assert_eq!(take_pending(&pending).unwrap().unwrap(), "late\n");
+    }
+
+    #[test]
+    fn parse_range_inclusive_up_to_max() {
+        assert_eq!(
+            parse_range::<u8>("0..=255"),
+            Ok(InputRange::Inclusive(0..=255))
+        );
+        assert_eq!(
+            parse_range::<u8>("0..255"),
+            Ok(InputRange::Exclusive(0..255))
+        );
+        assert!(parse_range::<u8>("0..256").is_err());
+    }
+
+    #[test]
+    fn parse_range_negative_bounds() {
+        assert_eq!(
+            parse_range::<i8>("-128..=-1"),
+            Ok(InputRange::Inclusive(-128..=-1))
+        );
+        assert_eq!(
+            parse_range::<i32>(" -3 .. 3 "),
+            Ok(InputRange::Exclusive(-3..3))
+        );
+        assert_eq!(
+            parse_range::<f64>("-0.5..=0.5"),
+            Ok(InputRange::Inclusive(-0.5..=0.5))
+        );
+    }
+
+    #[test]
+    fn parse_range_start_greater_than_end() {
+        let err = "range start must not be greater than the end".to_string();
+        assert_eq!(parse_range::<i32>("5..4"), Err(err.clone()));
+        assert_eq!(parse_range::<i32>("5..=4"), Err(err));
+        assert_eq!(parse_range::<i32>("5..5"), Ok(InputRange::Exclusive(5..5)));
+        assert_eq!(
+            parse_range::<i32>("5..=5"),
+            Ok(InputRange::Inclusive(5..=5))
+        );
+    }
+
+    #[test]
+    fn parse_range_malformed() {
+        assert_eq!(
+            parse_range::<i32>("5-4"),
+            Err("expected a range like `start..end`".to_string())
+        );
+        assert!(parse_range::<i32>("..4").is_err());
+        assert!(parse_range::<i32>("1...4").is_err());
+    }
+
+    #[test]
+    fn round_floats_rounds_to_nearest() {
+        let input = Input::<u32>::new().round_floats();
+        assert_eq!(transcript(input, b"2.5\n").0.unwrap(), 3);
+        let input = Input::<i32>::new().round_floats();
+        assert_eq!(transcript(input, b"-2.4\n").0.unwrap(), -2);
+        let input = Input::<i32>::new().round_floats();
+        assert_eq!(transcript(input, b"-0.4\n").0.unwrap(), 0);
+        let input = Input::<u8>::new().round_floats();
+        assert_eq!(transcript(input, b"1e2\n").0.unwrap(), 100);
+    }
+
+    #[test]
+    fn round_floats_out_of_range() {
+        let input = Input::<u8>::new().prompt("> ").round_floats();
+        let (result, transcript) = transcript(input, b"255.6\n-0.6\n");
+        assert!(matches!(result, Err(CasualError::Eof)));
+        assert_eq!(
+            transcript,
+            "> Error: 256 is out of range\n> Error: -1 is out of range\n> "
+        );
+    }
+
+    #[test]
+    fn round_floats_keeps_parse_error() {
+        let input = Input::<u32>::new().prompt("> ").round_floats();
+        let (result, transcript) = transcript(input, b"inf\nabc\n");
+        assert!(matches!(result, Err(CasualError::Eof)));
+        assert_eq!(
+            transcript,
+            "> Error: invalid digit found in string\n\
+             > Error: invalid digit found in string\n> "
+        );
+    }
+
+    #[test]
+    fn number_word_units_and_tens() {
+        assert_eq!(number_word("zero"), Some(0));
+        assert_eq!(number_word("Seven"), Some(7));
+        assert_eq!(number_word("nineteen"), Some(19));
+        assert_eq!(number_word("twenty"), Some(20));
+        assert_eq!(number_word("NINETY"), Some(90));
+    }
+
+    #[test]
+    fn number_word_compound() {
+        assert_eq!(number_word("twenty-one"), Some(21));
+        assert_eq!(number_word("forty two"), Some(42));
+        assert_eq!(number_word(" ninety - nine "), Some(99));
+    }
+
+    #[test]
+    fn number_word_invalid() {
+        assert_eq!(number_word(""), None);
+        assert_eq!(number_word("eleventy"), None);
+        assert_eq!(number_word("twenty-zero"), None);
+        assert_eq!(number_word("twenty-ten"), None);
+        assert_eq!(number_word("one-two"), None);
+        assert_eq!(number_word("twenty one two"), None);
+        assert_eq!(number_word("one hundred"), None);
+        assert_eq!(number_word("7"), None);
+    }
+
+    #[test]
+    fn parse_numbers_separators() {
+        assert_eq!(parse_numbers("1 2 3"), Ok(vec![1.0, 2.0, 3.0]));
+        assert_eq!(parse_numbers("1,2, 3 ,,4"), Ok(vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(parse_numbers(" -1.5\t2e1 "), Ok(vec![-1.5, 20.0]));
+    }
+
+    #[test]
+    fn parse_numbers_invalid() {
+        assert_eq!(
+            parse_numbers("1 two 3"),
+            Err("`two` is not a number".to_string())
+        );
+        let err = Err("please enter at least one number".to_string());
+        assert_eq!(parse_numbers(""), err);
+        assert_eq!(parse_numbers(" , ,"), err);
+    }
+
+    #[test]
+    fn stats_new() {
+        assert_eq!(
+            Stats::new(&[3.0, -1.0, 4.0, 2.0]),
+            Stats {
+                min: -1.0,
+                max: 4.0,
+                sum: 8.0,
+                mean: 2.0,
+            }
+        );
+        assert_eq!(
+            Stats::new(&[5.0]),
+            Stats {
+                min: 5.0,
+                max: 5.0,
+                sum: 5.0,
+                mean: 5.0,
+            }
+        );
+    }
+
+    #[test]
+    fn slugify_separators() {
+        assert_eq!(slugify("Hello World"), "hello-world");
+        assert_eq!(
+            slugify("  snake_case -- and  spaces "),
+            "snake-case-and-spaces"
+        );
+        assert_eq!(slugify("Rust's \"Book\"!"), "rusts-book");
+    }
+
+    #[test]
+    fn slugify_unicode_and_empty() {
+        assert_eq!(slugify("Ünïcödé Straße"), "ünïcödé-straße");
+        assert_eq!(slugify("ÀB"), "àb");
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify("-_ !?"), "");
+    }
+
+    #[test]
+    fn lines_validation_error() {
+        let lines = lines::<u16>().matches_with(|port| match *port >= 1024 {
+            true => Ok(()),
+            false => Err("port is reserved".to_string()),
+        });
+        assert_eq!(lines.parse("8080").unwrap(), 8080);
+        assert!(matches!(
+            lines.parse("80"),
+            Err(CasualError::Validation(msg)) if msg == "port is reserved"
+        ));
+        assert!(matches!(lines.parse("http"), Err(CasualError::Parse(_))));
+    }
+
+    #[test]
+    fn interrupted_read_is_not_cancelled() {
+        let err = CasualError::from(io::Error::from(io::ErrorKind::Interrupted));
+        assert!(matches!(err, CasualError::Io(_)));
+        assert!(matches!(
+            CasualError::from(term::interrupt()),
+            CasualError::Cancelled
+        ));
+    }
 }
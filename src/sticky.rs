@@ -0,0 +1,55 @@
+//! Persistence for values that are remembered between runs.
+//!
+//! Each value is stored as a file named after its key in a `casual`
+//! directory inside the user's cache directory. Any errors reading or writing
+//! these files are ignored, the value is simply not remembered.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/////////////////////////////////////////////////////////////////////////
+// Implementations
+/////////////////////////////////////////////////////////////////////////
+
+/// Returns the directory that values are stored in.
+fn cache_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+    base.map(|base| base.join("casual"))
+}
+
+/// Returns the file that the value for the given key is stored in.
+fn path(key: &str) -> Option<PathBuf> {
+    // only allow characters that are safe in a file name on all platforms
+    let name: String = key
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    cache_dir().map(|dir| dir.join(name))
+}
+
+/// Load the last value saved for the given key.
+pub fn load(key: &str) -> Option<String> {
+    let contents = fs::read_to_string(path(key)?).ok()?;
+    Some(contents.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Save a value for the given key.
+pub fn save(key: &str, value: &str) {
+    if let Some(path) = path(key) {
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(path, format!("{}\n", value));
+    }
+}
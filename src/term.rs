@@ -0,0 +1,383 @@
+//! Minimal raw terminal support.
+//!
+//! On Unix raw mode is enabled using the `stty` utility, and on Windows by
+//! changing the console mode. Callers are expected to fall back to normal
+//! line reading if it is not available, unless the input must not be
+//! displayed.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, IsTerminal, Read, Write};
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+use crate::{LineTooLong, ReadLimit};
+
+/////////////////////////////////////////////////////////////////////////
+// Definitions
+/////////////////////////////////////////////////////////////////////////
+
+/// A key pressed by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// A printable character.
+    Char(char),
+    /// The backspace key.
+    Backspace,
+    /// The enter key.
+    Enter,
+    /// Ctrl-D, or the end of input.
+    Eof,
+    /// Ctrl-C.
+    Interrupt,
+    /// Any other key or escape sequence.
+    Other,
+}
+
+/// The error used when the user presses Ctrl-C.
+#[derive(Debug)]
+struct Interrupt;
+
+/// Puts the terminal in raw mode while it is alive.
+pub struct RawMode {
+    #[cfg(unix)]
+    saved: String,
+    /// The console input handle and its original mode.
+    #[cfg(windows)]
+    input: (console::Handle, u32),
+    /// The console output handle and its original mode, if it was changed.
+    #[cfg(windows)]
+    output: Option<(console::Handle, u32)>,
+}
+
+/////////////////////////////////////////////////////////////////////////
+// Implementations
+/////////////////////////////////////////////////////////////////////////
+
+/// The number of times an interrupted read is retried before giving up.
+const READ_RETRIES: usize = 3;
+
+impl Display for Interrupt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "interrupted")
+    }
+}
+
+impl Error for Interrupt {}
+
+/// Returns the error used when the user presses Ctrl-C.
+pub fn interrupt() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, Interrupt)
+}
+
+/// Returns whether the error was caused by the user pressing Ctrl-C, rather
+/// than by a read that was interrupted, for example by a signal.
+pub fn is_interrupt(err: &io::Error) -> bool {
+    err.get_ref().is_some_and(|err| err.is::<Interrupt>())
+}
+
+/// Returns whether stdin and stdout are both connected to a terminal.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+#[cfg(unix)]
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("failed to configure terminal"));
+    }
+    String::from_utf8(output.stdout).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(windows)]
+mod console {
+    use std::ffi::c_void;
+    use std::io;
+
+    pub type Handle = *mut c_void;
+
+    pub const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+    pub const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+
+    pub const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+    pub const ENABLE_LINE_INPUT: u32 = 0x0002;
+    pub const ENABLE_ECHO_INPUT: u32 = 0x0004;
+    pub const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+    }
+
+    /// Returns the given standard handle and its console mode.
+    pub fn mode(std_handle: u32) -> io::Result<(Handle, u32)> {
+        let mut mode = 0;
+        // SAFETY: GetConsoleMode() fails for a handle that is invalid or not
+        // a console, and only writes to `mode`.
+        unsafe {
+            let handle = GetStdHandle(std_handle);
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok((handle, mode))
+        }
+    }
+
+    /// Sets the console mode of the given handle.
+    pub fn set_mode(handle: Handle, mode: u32) -> io::Result<()> {
+        // SAFETY: the handle was returned by mode().
+        match unsafe { SetConsoleMode(handle, mode) } {
+            0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl RawMode {
+    /// Enable raw mode, keystrokes are not echoed and are available
+    /// immediately.
+    #[cfg(unix)]
+    pub fn enable() -> io::Result<Self> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Ok(Self { saved })
+    }
+
+    /// Enable raw mode, keystrokes are not echoed and are available
+    /// immediately.
+    #[cfg(windows)]
+    pub fn enable() -> io::Result<Self> {
+        use self::console::*;
+        let (handle, saved) = mode(STD_INPUT_HANDLE)?;
+        let raw = saved & !(ENABLE_ECHO_INPUT | ENABLE_LINE_INPUT | ENABLE_PROCESSED_INPUT);
+        set_mode(handle, raw)?;
+        let input = (handle, saved);
+        // escape sequences are used to redraw the line, if the console does
+        // not support them only masked input displays correctly
+        let output = mode(STD_OUTPUT_HANDLE).ok().filter(|&(handle, saved)| {
+            set_mode(handle, saved | ENABLE_VIRTUAL_TERMINAL_PROCESSING).is_ok()
+        });
+        Ok(Self { input, output })
+    }
+
+    /// Raw mode is not supported on this platform.
+    #[cfg(not(any(unix, windows)))]
+    pub fn enable() -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "raw mode is not supported on this platform",
+        ))
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        let _ = stty(&[&self.saved]);
+        #[cfg(windows)]
+        {
+            let (handle, saved) = self.input;
+            let _ = console::set_mode(handle, saved);
+            if let Some((handle, saved)) = self.output {
+                let _ = console::set_mode(handle, saved);
+            }
+        }
+    }
+}
+
+/// Read a single byte, retrying a few times if the read is interrupted.
+fn read_byte<R: Read>(reader: &mut R) -> io::Result<Option<u8>> {
+    let mut buf = [0];
+    let mut retries = 0;
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(buf[0])),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted && retries < READ_RETRIES => {
+                retries += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Read a single key press from a reader in raw mode.
+pub fn read_key<R: Read>(reader: &mut R) -> io::Result<Key> {
+    let byte = match read_byte(reader)? {
+        Some(byte) => byte,
+        None => return Ok(Key::Eof),
+    };
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x03 => Key::Interrupt,
+        0x04 => Key::Eof,
+        0x1b => {
+            // skip the rest of the escape sequence, e.g. an arrow key
+            if let Some(b'[') | Some(b'O') = read_byte(reader)? {
+                while let Some(byte) = read_byte(reader)? {
+                    if (0x40..=0x7e).contains(&byte) {
+                        break;
+                    }
+                }
+            }
+            Key::Other
+        }
+        byte if byte < 0x20 => Key::Other,
+        byte => {
+            let mut buf = vec![byte];
+            let len = match byte {
+                0xf0..=0xff => 4,
+                0xe0..=0xef => 3,
+                0xc0..=0xdf => 2,
+                _ => 1,
+            };
+            while buf.len() < len {
+                match read_byte(reader)? {
+                    Some(byte) => buf.push(byte),
+                    None => break,
+                }
+            }
+            match String::from_utf8(buf) {
+                Ok(s) => s.chars().next().map_or(Key::Other, Key::Char),
+                Err(_) => Key::Other,
+            }
+        }
+    };
+    Ok(key)
+}
+
+/// Read a line in raw mode, calling `redraw` whenever the line changes.
+///
+/// The returned line has a trailing newline, unless the end of input was
+/// reached on an empty line, just like `BufRead::read_line`. Characters that
+/// would make the line longer than the limit are ignored. If the limit does
+/// not truncate, a `LineTooLong` error is returned once enter is pressed.
+pub fn edit_line<R, F>(
+    reader: &mut R,
+    out: &mut dyn Write,
+    limit: Option<ReadLimit>,
+    mut redraw: F,
+) -> io::Result<String>
+where
+    R: Read,
+    F: FnMut(&str, &mut dyn Write) -> io::Result<()>,
+{
+    let mut line = String::new();
+    let mut too_long = false;
+    redraw(&line, out)?;
+    loop {
+        match read_key(reader)? {
+            Key::Char(c) => {
+                if let Some(limit) = limit.filter(|limit| line.len() + c.len_utf8() > limit.bytes) {
+                    too_long |= !limit.truncate;
+                    continue;
+                }
+                line.push(c)
+            }
+            Key::Backspace => {
+                if line.pop().is_none() {
+                    continue;
+                }
+            }
+            Key::Enter if too_long => {
+                let bytes = limit.map_or(0, |limit| limit.bytes);
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    LineTooLong(bytes),
+                ));
+            }
+            Key::Enter => {
+                line.push('\n');
+                break;
+            }
+            Key::Eof if line.is_empty() => break,
+            Key::Interrupt => return Err(interrupt()),
+            Key::Eof | Key::Other => continue,
+        }
+        redraw(&line, out)?;
+    }
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader that is interrupted a number of times before reading.
+    struct Interrupted<'a> {
+        remaining: usize,
+        bytes: &'a [u8],
+    }
+
+    impl Read for Interrupted<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.remaining > 0 {
+                self.remaining -= 1;
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            self.bytes.read(buf)
+        }
+    }
+
+    #[test]
+    fn read_key_retries_interrupted() {
+        let mut reader = Interrupted {
+            remaining: 1,
+            bytes: b"a",
+        };
+        assert_eq!(read_key(&mut reader).unwrap(), Key::Char('a'));
+        assert_eq!(read_key(&mut reader).unwrap(), Key::Eof);
+    }
+
+    #[test]
+    fn read_key_gives_up_when_always_interrupted() {
+        let mut reader = Interrupted {
+            remaining: READ_RETRIES + 1,
+            bytes: b"a",
+        };
+        let err = read_key(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(!is_interrupt(&err));
+    }
+
+    #[test]
+    fn edit_line_ctrl_c_is_interrupt() {
+        let err = edit_line(&mut &b"ab\x03"[..], &mut io::sink(), None, |_, _| Ok(())).unwrap_err();
+        assert!(is_interrupt(&err));
+    }
+
+    #[test]
+    fn edit_line_over_limit() {
+        let limit = ReadLimit {
+            bytes: 3,
+            truncate: false,
+        };
+        let mut reader = &b"abcd\rab\r"[..];
+        let err = edit_line(&mut reader, &mut io::sink(), Some(limit), |_, _| Ok(())).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.get_ref().unwrap().is::<LineTooLong>());
+        // the next line is read from where the long line ended
+        let line = edit_line(&mut reader, &mut io::sink(), Some(limit), |_, _| Ok(()));
+        assert_eq!(line.unwrap(), "ab\n");
+    }
+
+    #[test]
+    fn edit_line_truncates() {
+        let limit = ReadLimit {
+            bytes: 3,
+            truncate: true,
+        };
+        let line = edit_line(&mut &b"abcd\r"[..], &mut io::sink(), Some(limit), |_, _| {
+            Ok(())
+        });
+        assert_eq!(line.unwrap(), "abc\n");
+    }
+}